@@ -13,12 +13,18 @@ use colored::Colorize;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use std::collections::HashMap;
 use std::env;
 use std::fs::File;
 use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
 
-pub use tools::{perform_google_search, read_website_content};
+use crate::http::HttpOptions;
+use crate::player::PlayerOptions;
+pub use tools::{build_tool_client, perform_google_search, read_website_content};
 
 #[derive(Debug, Serialize, Clone)]
 struct GeminiRequest {
@@ -125,38 +131,166 @@ struct ResponseFunctionCall {
     args: Value,
 }
 
+/// Fields that can be included per episode in the payload sent to the AI
+///
+/// - `title`, `topic`, `channel`: as returned by MediathekViewWeb
+/// - `duration`: duration in seconds
+/// - `url`: medium quality video URL
+/// - `description`: full episode description
+/// - `timestamp`: broadcast time as Unix timestamp
+/// - `date`: broadcast date as `YYYY-MM-DD HH:MM`
+pub const EPISODE_FIELDS: &[&str] = &[
+    "title",
+    "topic",
+    "duration",
+    "channel",
+    "url",
+    "description",
+    "timestamp",
+    "date",
+];
+
+/// Fields sent to the AI when nothing else is configured
+pub const DEFAULT_EPISODE_FIELDS: &[&str] = &["title", "topic", "duration", "channel", "url"];
+
+/// Gemini requests made before a rate limit error is given up on
+const GEMINI_MAX_ATTEMPTS: u32 = 3;
+
+/// Wait before the first retry after a rate limit, doubled for each further one
+const GEMINI_RETRY_BASE_DELAY: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Upper bound for a wait, also for a server's `Retry-After`
+const GEMINI_RETRY_MAX_DELAY: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Gemini model used unless `--ai-model` or `MWB_GEMINI_MODEL` name another
+pub const DEFAULT_MODEL: &str = "gemini-2.5-flash-lite";
+
+/// Episodes sent to the AI unless `--ai-max-episodes` says otherwise
+pub const DEFAULT_MAX_EPISODES: usize = 20;
+
+/// Pages kept in the per-run `read_website_content` cache
+const PAGE_CACHE_MAX_ENTRIES: usize = 32;
+
+/// Limit for a Gemini request unless `--timeout` sets another
+const GEMINI_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(120);
+
+/// A `key=` URL parameter, as found in request errors
+static KEY_PARAM_RE: LazyLock<regex::Regex> =
+    LazyLock::new(|| regex::Regex::new(r"([?&]key=)[^&\s)]+").expect("valid regex"));
+
+/// Options controlling how the AI processor builds its requests
+#[derive(Debug, Clone)]
+pub struct AIOptions {
+    /// Per-episode fields included in the payload, a subset of `EPISODE_FIELDS`
+    pub episode_fields: Vec<String>,
+    /// Append search hints to the model's web search queries
+    pub enhance_query: bool,
+    /// Directory where the text extracted by each `read_website_content` call is saved
+    pub dump_extract_dir: Option<PathBuf>,
+    /// Re-sort the final playlist locally when the AI's order isn't ascending
+    pub resort_episodes: bool,
+    /// Most episodes included in the payload, 0 for all of them
+    pub max_episodes: usize,
+    /// Gemini model from `--ai-model`, falls back to `MWB_GEMINI_MODEL` and `DEFAULT_MODEL`
+    pub model: Option<String>,
+    /// Episode list page read before the conversation starts, replacing the mandatory search
+    pub wiki_url: Option<String>,
+    /// Player that opens the finished playlist
+    pub player: PlayerOptions,
+    /// Proxy and timeouts from `--proxy` and `--timeout`, for Gemini and the tools
+    pub http: HttpOptions,
+}
+
+impl Default for AIOptions {
+    fn default() -> Self {
+        Self {
+            episode_fields: DEFAULT_EPISODE_FIELDS
+                .iter()
+                .map(|f| f.to_string())
+                .collect(),
+            enhance_query: true,
+            dump_extract_dir: None,
+            resort_episodes: false,
+            max_episodes: DEFAULT_MAX_EPISODES,
+            model: None,
+            wiki_url: None,
+            player: PlayerOptions::default(),
+            http: HttpOptions::default(),
+        }
+    }
+}
+
 /// Main AI processor that handles the chronological sorting task
 pub struct AIProcessor {
     client: Client,
+    tool_client: Client,
     api_key: String,
     base_url: String,
-    search_info: Option<String>,
+    /// `--vlc-ai` hints, in command line order
+    search_info: Vec<String>,
+    options: AIOptions,
+    /// Whether this run has searched (or read `--wiki-url`), gating `read_website_content`
+    search_performed: AtomicBool,
+    /// Extracted page text by URL, so a page the model asks for again isn't fetched twice
+    page_cache: Mutex<HashMap<String, String>>,
 }
 
 impl AIProcessor {
-    /// Create a new AI processor with optional search info
-    pub async fn new_with_verbose(search_info: Option<&str>) -> Result<Self> {
+    /// Create a new AI processor with the given search hints (may be empty)
+    pub async fn new_with_verbose(search_info: &[String], options: AIOptions) -> Result<Self> {
         let api_key = env::var("GOOGLE_API_KEY")
             .map_err(|_| {
                 Self::handle_api_key_error();
                 anyhow::anyhow!("GOOGLE_API_KEY environment variable not found. Please set it in a .env file or environment.")
             })?;
 
-        let client = Client::builder()
+        let client = options
+            .http
+            .client_builder(GEMINI_TIMEOUT)
             .user_agent("mwb-cli/1.0")
-            .timeout(std::time::Duration::from_secs(120))
             .build()?;
 
-        let base_url = "https://generativelanguage.googleapis.com/v1beta/models/gemini-2.5-flash-lite:generateContent".to_string();
+        let model = Self::resolve_model(options.model.as_deref())?;
+        let base_url = format!(
+            "https://generativelanguage.googleapis.com/v1beta/models/{model}:generateContent"
+        );
+        tracing::info!(model = %model, url = %base_url, "Using Gemini model");
 
         Ok(Self {
             client,
+            tool_client: build_tool_client(&options.http)?,
             api_key,
             base_url,
-            search_info: search_info.map(|s| s.to_string()),
+            search_info: search_info.to_vec(),
+            options,
+            search_performed: AtomicBool::new(false),
+            page_cache: Mutex::new(HashMap::new()),
         })
     }
 
+    /// Pick the Gemini model: `--ai-model`, then `MWB_GEMINI_MODEL`, then `DEFAULT_MODEL`
+    fn resolve_model(flag: Option<&str>) -> Result<String> {
+        let (model, source) = match flag {
+            Some(model) => (model.to_string(), "--ai-model"),
+            None => match env::var("MWB_GEMINI_MODEL") {
+                Ok(model) => (model, "MWB_GEMINI_MODEL"),
+                Err(_) => return Ok(DEFAULT_MODEL.to_string()),
+            },
+        };
+
+        let model = model.trim();
+        if model.is_empty() {
+            anyhow::bail!("{source} is empty, name a Gemini model like {DEFAULT_MODEL}");
+        }
+        if model.contains(|c: char| c.is_whitespace() || c == '/' || c == ':') {
+            anyhow::bail!(
+                "{source} '{model}' is not a Gemini model name (expected e.g. {DEFAULT_MODEL})"
+            );
+        }
+
+        Ok(model.to_string())
+    }
+
     /// Process TV show/series results with AI for chronological sorting and VLC playlist creation
     pub async fn process_episodes(
         &self,
@@ -171,17 +305,17 @@ impl AIProcessor {
             results.len()
         );
 
+        // Tool order is tracked per run
+        self.search_performed.store(false, Ordering::Relaxed);
+
         // Convert results to a more structured format for the AI
         let episodes_json = self.format_episodes_for_ai(results)?;
 
-        let search_hint = if let Some(search_info) = &self.search_info {
-            if !search_info.is_empty() {
-                format!("s{}", search_info)
-            } else {
-                String::new()
-            }
-        } else {
+        // Several hints (e.g. one per series) are passed as separate paragraphs
+        let search_hint = if self.search_info.is_empty() {
             String::new()
+        } else {
+            format!("s{}", self.search_info.join("\n\n"))
         };
 
         let mut system_prompt = r#"# TV-Serien-Analyse und VLC-Playlist-Erstellung
@@ -216,6 +350,10 @@ Entfernen Sie Duplikate basierend auf:
 
 Extrahieren Sie aus den Eingabedaten: `title`, `url_video`, `description`, `duration`, `channel`, `topic`"#.to_string();
 
+        if self.includes_field("date") || self.includes_field("timestamp") {
+            system_prompt.push_str("\n\n**Ausstrahlungsdatum**: Die Felder `date`/`timestamp` enthalten das Sendedatum. Nutzen Sie es für die chronologische Sortierung, wenn keine Episodenkennung vorhanden ist.");
+        }
+
         if !search_hint.is_empty() {
             system_prompt.push_str(&format!(
                 "\n\n**alle bekannten Episoden und gewünschte reihenfolge der playlist**: {}",
@@ -223,11 +361,33 @@ Extrahieren Sie aus den Eingabedaten: `title`, `url_video`, `description`, `dura
             ));
         }
 
-        let user_prompt = format!(
+        let mut user_prompt = format!(
             "**AUFTRAG**: Erstellen Sie eine VLC-Playlist mit den bereitgestellten Episoden. Deduplizieren Sie intelligent und sortieren Sie chronologisch (älteste → neueste). Verwenden Sie die Episodenreihenfolge aus dem Clipboard.\n\n**Episodendaten**:\n{}",
             episodes_json
         );
 
+        // A page given with --wiki-url is read up front and replaces the search
+        if let Some(url) = &self.options.wiki_url {
+            println!("📖 Reading episode list from {}", url);
+            let content = self
+                .read_source(url)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to read --wiki-url {}: {}", url, e))?;
+            self.search_performed.store(true, Ordering::Relaxed);
+            user_prompt.push_str(&format!(
+                "\n\n**Episodenliste von {}** (bereits gelesen, keine Suche nötig, Sie können direkt die Playlist erstellen):\n{}",
+                url, content
+            ));
+        }
+
+        // The German Wikipedia article of the dominant topic is read on the first search,
+        // unless the episode list was given with --wiki-url
+        let mut pinned_series = if self.options.wiki_url.is_some() {
+            None
+        } else {
+            Self::dominant_topic(results)
+        };
+
         let tools = self.create_tools();
         let mut conversation_history = vec![Content {
             role: "user".to_string(),
@@ -247,11 +407,16 @@ Extrahieren Sie aus den Eingabedaten: `title`, `url_video`, `description`, `dura
         // Main conversation loop with tool calling
         let max_iterations = 8; // Increased to allow for proper tool usage
         for iteration in 1..=max_iterations {
-            if iteration == 1 {
+            if iteration == 1 && self.options.wiki_url.is_none() {
                 println!(
                     "🔄 Iteration {} - Initial request (expecting search tool call)...",
                     iteration
                 );
+            } else if iteration == 1 {
+                println!(
+                    "🔄 Iteration {} - Initial request with the episode list from --wiki-url...",
+                    iteration
+                );
             } else {
                 println!("🔄 Iteration {} - Continuing conversation...", iteration);
             }
@@ -313,7 +478,14 @@ Extrahieren Sie aus den Eingabedaten: `title`, `url_video`, `description`, `dura
                                 );
                             }
 
-                            let tool_result = self.execute_function_call(function_call).await?;
+                            let pinned = if function_call.name == "perform_google_search" {
+                                pinned_series.take()
+                            } else {
+                                None
+                            };
+                            let tool_result = self
+                                .execute_function_call(function_call, pinned.as_deref())
+                                .await?;
 
                             // Add the model's request to history
                             conversation_history.push(Content {
@@ -339,7 +511,7 @@ Extrahieren Sie aus den Eingabedaten: `title`, `url_video`, `description`, `dura
                         }
                         ResponsePart::Text { text } => {
                             // Check if the AI tried to provide a final answer without using required tools
-                            if iteration == 1 {
+                            if iteration == 1 && self.options.wiki_url.is_none() {
                                 println!("❌ AI provided text response instead of calling perform_google_search first!");
 
                                 // Add the model's response to history
@@ -395,31 +567,91 @@ Extrahieren Sie aus den Eingabedaten: `title`, `url_video`, `description`, `dura
     }
 
     /// Make HTTP request to Gemini API
+    ///
+    /// The API key is sent as a header rather than in the URL, so it can't end up
+    /// in request errors, and every error message is passed through `redact_api_key`.
+    ///
+    /// Rate limit errors are retried up to `GEMINI_MAX_ATTEMPTS` times, waiting as
+    /// long as the server's `Retry-After` asks or with exponential backoff and jitter.
     async fn call_gemini_api(&self, request: &GeminiRequest) -> Result<GeminiResponse> {
-        let url = format!("{}?key={}", self.base_url, self.api_key);
-
-        let response = self
-            .client
-            .post(&url)
-            .header("Content-Type", "application/json")
-            .json(request)
-            .send()
-            .await?;
+        let mut attempt = 1;
+        let response = loop {
+            let response = self
+                .client
+                .post(&self.base_url)
+                .header("Content-Type", "application/json")
+                .header("x-goog-api-key", &self.api_key)
+                .json(request)
+                .send()
+                .await
+                .map_err(|e| {
+                    anyhow::anyhow!(
+                        "Gemini API request failed: {}",
+                        Self::redact_api_key(&e.to_string(), &self.api_key)
+                    )
+                })?;
+
+            if response.status().is_success() {
+                break response;
+            }
 
-        if !response.status().is_success() {
             let status = response.status();
+            let retry_after = Self::retry_after(response.headers());
             let error_text = response.text().await.unwrap_or_default();
+
+            if attempt < GEMINI_MAX_ATTEMPTS && Self::is_rate_limited(status, &error_text) {
+                let delay = retry_after
+                    .unwrap_or_else(|| Self::backoff_delay(attempt))
+                    .min(GEMINI_RETRY_MAX_DELAY);
+                tracing::warn!(
+                    status = %status,
+                    attempt = %attempt,
+                    delay_ms = %delay.as_millis(),
+                    from_retry_after = %retry_after.is_some(),
+                    "Gemini API rate limited, retrying"
+                );
+                println!(
+                    "{}",
+                    format!(
+                        "⏳ Gemini API rate limit hit, retrying in {:.1}s (attempt {}/{})",
+                        delay.as_secs_f64(),
+                        attempt + 1,
+                        GEMINI_MAX_ATTEMPTS
+                    )
+                    .yellow()
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+                continue;
+            }
+
             return Err(anyhow::anyhow!(
                 "Gemini API error {}: {}",
                 status,
-                error_text
+                Self::redact_api_key(&error_text, &self.api_key)
             ));
-        }
+        };
 
-        let gemini_response: GeminiResponse = response.json().await?;
+        let gemini_response: GeminiResponse = response.json().await.map_err(|e| {
+            anyhow::anyhow!(
+                "Invalid Gemini API response: {}",
+                Self::redact_api_key(&e.to_string(), &self.api_key)
+            )
+        })?;
         Ok(gemini_response)
     }
 
+    /// Replace the API key and any `key=` URL parameter in text with `***`
+    fn redact_api_key(text: &str, api_key: &str) -> String {
+        let text = if api_key.is_empty() {
+            text.to_string()
+        } else {
+            text.replace(api_key, "***")
+        };
+
+        KEY_PARAM_RE.replace_all(&text, "${1}***").into_owned()
+    }
+
     /// Create tool definitions for the Gemini API
     fn create_tools(&self) -> Vec<Tool> {
         vec![
@@ -441,7 +673,7 @@ Extrahieren Sie aus den Eingabedaten: `title`, `url_video`, `description`, `dura
                     },
                     FunctionDeclaration {
                         name: "read_website_content".to_string(),
-                        description: "MANDATORY SECOND TOOL: Read and extract text content from a website URL. Use this IMMEDIATELY after perform_google_search to get detailed episode information from Wikipedia You find a table with episode details. The sequence of episodes is listed in chronological order.".to_string(),
+                        description: "MANDATORY SECOND TOOL: Read and extract text content from a website URL. Use this IMMEDIATELY after perform_google_search to get detailed episode information from Wikipedia You find a table with episode details. The sequence of episodes is listed in chronological order. Linked Wikipedia episode lists and season subpages are read automatically and appended. Wikipedia episode tables are returned as a JSON list of {episode_no, title, air_date} objects in page order.".to_string(),
                         parameters: Parameters {
                             r#type: "object".to_string(),
                             properties: json!({
@@ -490,35 +722,109 @@ Extrahieren Sie aus den Eingabedaten: `title`, `url_video`, `description`, `dura
     /// Format episodes for AI processing
     fn format_episodes_for_ai(&self, results: &[mediathekviewweb::models::Item]) -> Result<String> {
         // Limit episodes to prevent token overflow
-        let limited_results = if results.len() > 20 {
-            &results[..20]
-        } else {
-            results
+        let limited_results = match self.options.max_episodes {
+            0 => results,
+            max => &results[..results.len().min(max)],
         };
 
         let formatted: Vec<Value> = limited_results
             .iter()
             .map(|item| {
-                json!({
-                    "title": item.title,
-                    "topic": item.topic,
-                    "duration": item.duration,
-                    "channel": item.channel,
-                    "url": item.url_video,
-                })
+                let mut episode = serde_json::Map::new();
+                for field in &self.options.episode_fields {
+                    let value = match field.as_str() {
+                        "title" => json!(item.title),
+                        "topic" => json!(item.topic),
+                        "duration" => json!(item.duration.map(|d| d.as_secs())),
+                        "channel" => json!(item.channel),
+                        "url" => json!(item.url_video),
+                        "description" => json!(item.description),
+                        "timestamp" => json!(item.timestamp),
+                        "date" => json!(chrono::DateTime::from_timestamp(item.timestamp, 0)
+                            .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())),
+                        _ => continue,
+                    };
+                    episode.insert(field.clone(), value);
+                }
+                Value::Object(episode)
             })
             .collect();
 
-        if results.len() > 20 {
-            println!("ℹ️  Processing first 20 episodes to avoid API limits. Use smaller -s parameter for full dataset.");
+        let omitted = results.len() - limited_results.len();
+        if omitted > 0 {
+            println!(
+                "{}",
+                format!(
+                    "ℹ️  Sending the first {} of {} episodes to the AI, {} omitted. Raise --ai-max-episodes (0 = no limit) to include them.",
+                    limited_results.len(),
+                    results.len(),
+                    omitted
+                )
+                .yellow()
+            );
+        } else {
+            println!("ℹ️  Sending all {} episodes to the AI", results.len());
         }
+        tracing::info!(
+            included = %limited_results.len(),
+            omitted = %omitted,
+            max_episodes = %self.options.max_episodes,
+            "Episodes formatted for AI"
+        );
 
         serde_json::to_string_pretty(&formatted)
             .map_err(|e| anyhow::anyhow!("Failed to serialize episodes: {}", e))
     }
 
+    /// Save text extracted from a website to `dir`, named by host and timestamp
+    fn dump_extract(dir: &Path, url: &str, content: &str) -> Result<()> {
+        std::fs::create_dir_all(dir)?;
+
+        let host = url::Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(|h| h.to_string()))
+            .unwrap_or_else(|| "unknown".to_string());
+        let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S_%3f");
+        let path = dir.join(format!("{}_{}.txt", host, timestamp));
+
+        std::fs::write(&path, format!("URL: {}\n\n{}", url, content))?;
+
+        tracing::info!(path = %path.display(), url = %url, "Dumped extracted website content");
+        eprintln!("📝 Extracted content saved to {}", path.display());
+
+        Ok(())
+    }
+
+    /// Whether a field is part of the per-episode AI payload
+    fn includes_field(&self, field: &str) -> bool {
+        self.options.episode_fields.iter().any(|f| f == field)
+    }
+
+    /// Most frequent topic among the results, used as the series name
+    fn dominant_topic(results: &[mediathekviewweb::models::Item]) -> Option<String> {
+        let mut counts: std::collections::HashMap<&str, usize> = std::collections::HashMap::new();
+        for item in results {
+            let topic = item.topic.trim();
+            if !topic.is_empty() {
+                *counts.entry(topic).or_insert(0) += 1;
+            }
+        }
+
+        counts
+            .into_iter()
+            .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(a.0)))
+            .map(|(topic, _)| topic.to_string())
+    }
+
     /// Execute a function call from the AI
-    async fn execute_function_call(&self, call: &ResponseFunctionCall) -> Result<FunctionResponse> {
+    ///
+    /// `pinned_series` is passed to the first web search so the series' German
+    /// Wikipedia article is tried before a general search.
+    async fn execute_function_call(
+        &self,
+        call: &ResponseFunctionCall,
+        pinned_series: Option<&str>,
+    ) -> Result<FunctionResponse> {
         let function_name = &call.name;
         let args = &call.args;
 
@@ -528,12 +834,11 @@ Extrahieren Sie aus den Eingabedaten: `title`, `url_video`, `description`, `dura
             "Executing AI tool call"
         );
 
-        // Enforce tool usage order - read_website_content cannot be called before perform_google_search
-        if function_name == "read_website_content" {
-            let search_tool_used = std::env::var("SEARCH_TOOL_USED").unwrap_or_default() == "1";
-            if !search_tool_used {
-                return Err(anyhow::anyhow!("ERROR: You must use perform_google_search BEFORE using read_website_content. Please search for information first, then read the discovered URLs."));
-            }
+        // Enforce tool usage order - read_website_content cannot be called before perform_google_search,
+        // a page given with --wiki-url counts as a search
+        if function_name == "read_website_content" && !self.search_performed.load(Ordering::Relaxed)
+        {
+            return Err(anyhow::anyhow!("ERROR: You must use perform_google_search BEFORE using read_website_content. Please search for information first, then read the discovered URLs."));
         }
 
         let result_string = match function_name.as_str() {
@@ -545,9 +850,16 @@ Extrahieren Sie aus den Eingabedaten: `title`, `url_video`, `description`, `dura
                 tracing::debug!(query = %query, "Performing Google search");
 
                 // Mark that search tool has been used
-                std::env::set_var("SEARCH_TOOL_USED", "1");
-
-                perform_google_search(query).await?
+                self.search_performed.store(true, Ordering::Relaxed);
+
+                perform_google_search(
+                    &self.tool_client,
+                    query,
+                    self.options.enhance_query,
+                    pinned_series,
+                    &self.search_info,
+                )
+                .await?
             }
             "read_website_content" => {
                 let url = args["url"]
@@ -556,7 +868,7 @@ Extrahieren Sie aus den Eingabedaten: `title`, `url_video`, `description`, `dura
 
                 tracing::debug!(url = %url, "Reading website content");
 
-                read_website_content(url).await?
+                self.read_source(url).await?
             }
             "create_vlc_playlist" => {
                 let episodes = args["episodes"]
@@ -584,10 +896,58 @@ Extrahieren Sie aus den Eingabedaten: `title`, `url_video`, `description`, `dura
         Ok(response)
     }
 
+    /// Read a page for the AI, saving the extracted text with `--ai-dump-extract`
+    ///
+    /// Pages already read in this run come from the page cache.
+    async fn read_source(&self, url: &str) -> Result<String> {
+        if let Some(content) = self.cached_page(url) {
+            tracing::info!(url = %url, length = %content.len(), "Page cache hit");
+            return Ok(content);
+        }
+
+        let content = read_website_content(&self.tool_client, url).await?;
+        self.cache_page(url, &content);
+
+        if let Some(dir) = &self.options.dump_extract_dir {
+            // A failed dump shouldn't abort the sort, it's only a debugging aid
+            if let Err(e) = Self::dump_extract(dir, url, &content) {
+                eprintln!(
+                    "{}",
+                    format!("⚠️  Failed to dump extracted content: {}", e).yellow()
+                );
+            }
+        }
+
+        Ok(content)
+    }
+
+    /// Text of a page read earlier in this run
+    fn cached_page(&self, url: &str) -> Option<String> {
+        self.page_cache
+            .lock()
+            .ok()
+            .and_then(|cache| cache.get(url).cloned())
+    }
+
+    /// Remember a page's text, unless the cache is full
+    fn cache_page(&self, url: &str, content: &str) {
+        let Ok(mut cache) = self.page_cache.lock() else {
+            return;
+        };
+        if cache.len() >= PAGE_CACHE_MAX_ENTRIES {
+            tracing::debug!(url = %url, max_entries = %PAGE_CACHE_MAX_ENTRIES, "Page cache full, not caching");
+            return;
+        }
+        cache.insert(url.to_string(), content.to_string());
+    }
+
     /// Create VLC playlist and launch VLC
     async fn create_vlc_playlist(&self, episodes: &[Value], playlist_name: &str) -> Result<String> {
         println!("🎵 Creating VLC playlist: {}", playlist_name);
 
+        let episodes = self.verify_episode_order(episodes);
+        let episodes = episodes.as_slice();
+
         // Generate timestamp for unique filename
         let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
         let filename = format!("{}_{}.xspf", playlist_name, timestamp);
@@ -617,7 +977,7 @@ Extrahieren Sie aus den Eingabedaten: `title`, `url_video`, `description`, `dura
 
                 // Get other metadata
                 let description = episode["description"].as_str().unwrap_or("");
-                let clean_desc = self.clean_description(description);
+                let clean_desc = Self::clean_description(description);
                 let channel = episode["channel"].as_str().unwrap_or("");
                 let topic = episode["topic"].as_str().unwrap_or("");
 
@@ -678,48 +1038,100 @@ Extrahieren Sie aus den Eingabedaten: `title`, `url_video`, `description`, `dura
             }
         }
 
-        // Try to launch VLC with the playlist
-        self.launch_vlc(&filename)?;
+        let launched = self.options.player.launch(&filename);
 
         Ok(format!(
-            "XSPF playlist '{}' created with {} episodes and VLC launched successfully!",
+            "XSPF playlist '{}' created with {} episodes{}",
             filename,
-            episodes.len()
+            episodes.len(),
+            if launched {
+                " and opened in the player"
+            } else {
+                ", the user opens it manually"
+            }
         ))
     }
 
-    /// Launch VLC with the playlist
-    fn launch_vlc(&self, playlist_path: &str) -> Result<()> {
-        println!("🚀 Launching VLC with playlist...");
+    /// Check that the AI's playlist is in ascending season/episode order
+    ///
+    /// Episodes without a marker are skipped in the check. On a violation a
+    /// warning is printed and, with `resort_episodes`, the playlist is sorted
+    /// locally (only possible when every episode carries a marker).
+    fn verify_episode_order(&self, episodes: &[Value]) -> Vec<Value> {
+        let markers: Vec<Option<(u32, u32)>> = episodes
+            .iter()
+            .map(|episode| episode["title"].as_str().and_then(episode_marker))
+            .collect();
+
+        let known: Vec<(usize, (u32, u32))> = markers
+            .iter()
+            .enumerate()
+            .filter_map(|(i, marker)| marker.map(|m| (i, m)))
+            .collect();
+        let violations: Vec<(usize, usize)> = known
+            .windows(2)
+            .filter(|pair| pair[1].1 < pair[0].1)
+            .map(|pair| (pair[0].0, pair[1].0))
+            .collect();
+
+        if violations.is_empty() {
+            tracing::info!(
+                episode_count = %episodes.len(),
+                with_marker = %known.len(),
+                "AI playlist order verified"
+            );
+            return episodes.to_vec();
+        }
 
-        // Try different VLC executable names/paths
-        let vlc_commands = vec![
-            "vlc",
-            "vlc.exe",
-            "C:\\Program Files\\VideoLAN\\VLC\\vlc.exe",
-            "C:\\Program Files (x86)\\VideoLAN\\VLC\\vlc.exe",
-        ];
+        let (before, after) = violations[0];
+        tracing::warn!(
+            violation_count = %violations.len(),
+            "AI playlist is not in ascending episode order"
+        );
+        println!(
+            "{}",
+            format!(
+                "⚠️  AI playlist is not in ascending episode order ({} violation(s)), e.g. '{}' comes before '{}'",
+                violations.len(),
+                episodes[before]["title"].as_str().unwrap_or(""),
+                episodes[after]["title"].as_str().unwrap_or("")
+            )
+            .yellow()
+        );
 
-        for vlc_cmd in &vlc_commands {
-            match Command::new(vlc_cmd).arg(playlist_path).spawn() {
-                Ok(_) => {
-                    println!("✅ VLC launched successfully with {}", vlc_cmd);
-                    return Ok(());
-                }
-                Err(_) => continue,
-            }
+        if !self.options.resort_episodes {
+            println!(
+                "{}",
+                "💡 Use --ai-resort to sort the playlist locally by season/episode markers".cyan()
+            );
+            return episodes.to_vec();
         }
 
-        // If VLC launch failed, provide helpful message
-        println!("⚠️  Could not auto-launch VLC. You can manually open the playlist:");
-        println!("   📁 File: {}", playlist_path);
-        println!("   💡 Tip: Add VLC to your PATH or install it to default location");
+        if known.len() != episodes.len() {
+            println!(
+                "{}",
+                format!(
+                    "⚠️  {} episode(s) have no season/episode marker, keeping the AI's order",
+                    episodes.len() - known.len()
+                )
+                .yellow()
+            );
+            return episodes.to_vec();
+        }
 
-        Ok(())
+        let mut sorted: Vec<(Option<(u32, u32)>, Value)> =
+            markers.into_iter().zip(episodes.iter().cloned()).collect();
+        sorted.sort_by_key(|(marker, _)| *marker);
+        println!(
+            "{}",
+            "🔃 Playlist re-sorted locally by season/episode".green()
+        );
+
+        sorted.into_iter().map(|(_, episode)| episode).collect()
     }
 
     /// Clean description text for XSPF format
-    fn clean_description(&self, description: &str) -> String {
+    fn clean_description(description: &str) -> String {
         // Remove line breaks, extra whitespace, and truncate to reasonable length
         let cleaned = description
             .replace(['\n', '\r'], " ")
@@ -727,9 +1139,10 @@ Extrahieren Sie aus den Eingabedaten: `title`, `url_video`, `description`, `dura
             .collect::<Vec<&str>>()
             .join(" ");
 
-        // Truncate to 300 characters for XSPF annotations (longer than M3U since XML handles it better)
-        if cleaned.len() > 300 {
-            format!("{}...", &cleaned[..297])
+        // Truncate to 300 characters for XSPF annotations (longer than M3U since XML handles it better),
+        // counting chars so umlauts never get split
+        if cleaned.chars().count() > 300 {
+            format!("{}...", cleaned.chars().take(297).collect::<String>())
         } else {
             cleaned
         }
@@ -763,6 +1176,39 @@ Extrahieren Sie aus den Eingabedaten: `title`, `url_video`, `description`, `dura
         }
     }
 
+    /// Whether a failed Gemini response is a (possibly transient) rate limit
+    fn is_rate_limited(status: reqwest::StatusCode, body: &str) -> bool {
+        let body = body.to_lowercase();
+        status == reqwest::StatusCode::TOO_MANY_REQUESTS
+            || body.contains("quota")
+            || body.contains("rate limit")
+    }
+
+    /// Wait requested by a `Retry-After` header, in seconds or as an HTTP date
+    fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<std::time::Duration> {
+        let value = headers
+            .get(reqwest::header::RETRY_AFTER)?
+            .to_str()
+            .ok()?
+            .trim();
+
+        if let Ok(seconds) = value.parse::<u64>() {
+            return Some(std::time::Duration::from_secs(seconds));
+        }
+
+        let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+        (date.with_timezone(&chrono::Utc) - chrono::Utc::now())
+            .to_std()
+            .ok()
+    }
+
+    /// Exponential backoff for the retry after `attempt`, with up to a second of jitter
+    /// so parallel runs don't retry in lockstep
+    fn backoff_delay(attempt: u32) -> std::time::Duration {
+        let jitter = std::time::Duration::from_millis(rand::random::<u64>() % 1000);
+        GEMINI_RETRY_BASE_DELAY * 2u32.pow(attempt - 1) + jitter
+    }
+
     /// Handle API key errors with helpful messages and browser opening
     fn handle_api_key_error() {
         println!("{}", "🔑 API Key Issue Detected!".yellow().bold());
@@ -871,3 +1317,84 @@ Extrahieren Sie aus den Eingabedaten: `title`, `url_video`, `description`, `dura
         }
     }
 }
+
+/// A `(S2/E10)` marker in a title
+static SEASON_EPISODE_RE: LazyLock<regex::Regex> =
+    LazyLock::new(|| regex::Regex::new(r"(?i)\(\s*S(\d+)\s*/\s*E(\d+)\s*\)").expect("valid regex"));
+
+/// A `(234)` episode number at the end of a title
+static TRAILING_NUMBER_RE: LazyLock<regex::Regex> =
+    LazyLock::new(|| regex::Regex::new(r"\((\d+)\)\s*$").expect("valid regex"));
+
+/// Season/episode marker of a title: `(S2/E10)` → (2, 10), a trailing `(234)` → (1, 234)
+///
+/// Trailing numbers that look like a production year are ignored.
+pub fn episode_marker(title: &str) -> Option<(u32, u32)> {
+    if let Some(caps) = SEASON_EPISODE_RE.captures(title) {
+        return Some((caps[1].parse().ok()?, caps[2].parse().ok()?));
+    }
+
+    let number: u32 = TRAILING_NUMBER_RE.captures(title)?[1].parse().ok()?;
+    if (1900..=2100).contains(&number) {
+        return None;
+    }
+    Some((1, number))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redact_api_key_hides_key_parameters_in_urls() {
+        let error = "error sending request for url (https://generativelanguage.googleapis.com/v1beta/models/gemini:generateContent?alt=json&key=AIzaSecret123)";
+        let redacted = AIProcessor::redact_api_key(error, "");
+
+        assert!(!redacted.contains("AIzaSecret123"));
+        assert!(redacted.ends_with("?alt=json&key=***)"));
+    }
+
+    #[test]
+    fn redact_api_key_hides_the_key_anywhere() {
+        let redacted = AIProcessor::redact_api_key(
+            "invalid key AIzaSecret123 for https://example.org/?key=other&x=1",
+            "AIzaSecret123",
+        );
+
+        assert_eq!(
+            redacted,
+            "invalid key *** for https://example.org/?key=***&x=1"
+        );
+    }
+
+    #[test]
+    fn clean_description_truncates_umlauts_by_character() {
+        // Two-byte umlauts around the 297th character, where a byte cut would panic
+        let description = format!("{}\n{}", "a".repeat(290), "äöüÄÖÜß".repeat(5));
+        let cleaned = AIProcessor::clean_description(&description);
+
+        assert_eq!(cleaned.chars().count(), 300);
+        assert!(cleaned.starts_with(&format!("{} äöüÄÖÜ", "a".repeat(290))));
+        assert!(cleaned.ends_with("äöüÄÖÜ..."));
+    }
+
+    #[test]
+    fn clean_description_keeps_short_text_on_one_line() {
+        assert_eq!(
+            AIProcessor::clean_description("Kommissarin  Lürsen\r\nermittelt"),
+            "Kommissarin Lürsen ermittelt"
+        );
+    }
+
+    #[test]
+    fn episode_marker_reads_season_episode_and_trailing_numbers() {
+        assert_eq!(episode_marker("Der Fall (S2/E10)"), Some((2, 10)));
+        assert_eq!(
+            episode_marker("Der Fall ( s03 / e4 ) - Hörfassung"),
+            Some((3, 4))
+        );
+        assert_eq!(episode_marker("Folge (234) "), Some((1, 234)));
+        assert_eq!(episode_marker("Der Film (1998)"), None);
+        assert_eq!(episode_marker("Ohne Nummer"), None);
+    }
+}
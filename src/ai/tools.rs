@@ -20,6 +20,7 @@
 //! - Host detection for site-specific extraction
 //! - CSS selector attempts and success rates
 //! - Content filtering and extraction results
+//! - Structured Wikipedia episode tables found
 //! - Character count summaries
 //! 
 //! ### Usage:
@@ -29,24 +30,145 @@
 
 use anyhow::Result;
 use reqwest::Client;
-use scraper::{Html, Selector};
+use scraper::{ElementRef, Html, Selector};
+use serde::Serialize;
 use serde_json::Value;
 
 use url::Url;
 
-/// Performs a web search using DuckDuckGo's instant answer API
-/// This is a free alternative to paid search APIs
-/// Enhanced for German TV series episode information
-pub async fn perform_google_search(query: &str) -> Result<String> {
-    tracing::info!(query = %query, "Starting web search");
+use crate::http::HttpOptions;
 
-    let enhanced_query = format!("{} wikipedia", query);
+/// Limit for a search or page request unless `--timeout` sets another
+const TOOL_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Build the HTTP client shared by all tool calls
+///
+/// Created once per AI session so connections and TLS sessions are reused
+/// across the model's multiple search and read requests.
+pub fn build_tool_client(http: &HttpOptions) -> Result<Client> {
+    Ok(http
+        .client_builder(TOOL_TIMEOUT)
+        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.124 Safari/537.36")
+        .build()?)
+}
+
+/// Performs a web search with the configured provider (see `SearchProvider`),
+/// by default DuckDuckGo's instant answer API as a free alternative to paid search APIs
+/// Enhanced for German TV series episode information unless `enhance_query` is false,
+/// in which case the model's query is searched verbatim
+///
+/// When `pinned_series` is given, the series' German Wikipedia article is read
+/// directly first and returned instead of general search results if it exists
+///
+/// Short `--vlc-ai` hints (see `hint_pages`) are appended to every result as
+/// pages to read, so the model finds the right article for each series.
+pub async fn perform_google_search(
+    client: &Client,
+    query: &str,
+    enhance_query: bool,
+    pinned_series: Option<&str>,
+    hints: &[String],
+) -> Result<String> {
+    let results = search_web(client, query, enhance_query, pinned_series).await?;
+
+    let pages = hint_pages(hints);
+    if pages.is_empty() {
+        return Ok(results);
+    }
+
+    tracing::debug!(pages = ?pages, "Appending pages from search hints");
+    Ok(format!(
+        "{}
+
+Pages suggested by the user (read these first):
+{}",
+        results,
+        pages
+            .iter()
+            .map(|page| format!("- {}", page))
+            .collect::<Vec<_>>()
+            .join("\n")
+    ))
+}
+
+/// Longest hint that is still treated as a series name or URL rather than
+/// pasted context like an episode list
+const MAX_SEARCH_HINT_LENGTH: usize = 100;
+
+/// Turn the short single-line hints into pages worth reading
+///
+/// URLs are kept as given, other short hints are taken as series names and
+/// mapped to their likely German Wikipedia article. Longer hints only go into
+/// the prompt.
+fn hint_pages(hints: &[String]) -> Vec<String> {
+    let mut pages: Vec<String> = Vec::new();
+
+    for hint in hints {
+        let hint = hint.trim();
+        if hint.is_empty() || hint.contains('\n') || hint.chars().count() > MAX_SEARCH_HINT_LENGTH {
+            continue;
+        }
+
+        let page = if hint.starts_with("http://") || hint.starts_with("https://") {
+            hint.to_string()
+        } else {
+            german_wikipedia_url(hint)
+        };
+
+        if !pages.contains(&page) {
+            pages.push(page);
+        }
+    }
+
+    pages
+}
+
+async fn search_web(
+    client: &Client,
+    query: &str,
+    enhance_query: bool,
+    pinned_series: Option<&str>,
+) -> Result<String> {
+    tracing::info!(query = %query, enhance_query = %enhance_query, "Starting web search");
+
+    if let Some(series) = pinned_series {
+        if let Some((url, content)) = read_german_wikipedia_article(client, series).await {
+            return Ok(format!(
+                "German Wikipedia article for '{}' (authoritative episode source):\nURL: {}\n\n{}",
+                series, url, content
+            ));
+        }
+    }
+
+    let enhanced_query = if enhance_query {
+        format!("{} wikipedia", query)
+    } else {
+        query.to_string()
+    };
 
     tracing::debug!(enhanced_query = %enhanced_query, "Enhanced search query");
 
-    let client = Client::builder()
-        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.124 Safari/537.36")
-        .build()?;
+    // A configured search API gives real results, DuckDuckGo is the fallback
+    let provider = SearchProvider::from_env();
+    tracing::info!(provider = %provider.name(), "Using search provider");
+    if !matches!(provider, SearchProvider::DuckDuckGo) {
+        match provider.search(client, &enhanced_query).await {
+            Ok(results) if !results.is_empty() => {
+                tracing::info!(
+                    provider = %provider.name(),
+                    result_count = %results.len(),
+                    "Search API successful"
+                );
+                return Ok(format_search_results(&results));
+            }
+            Ok(_) => {
+                tracing::warn!(provider = %provider.name(), "Search API found nothing, falling back to DuckDuckGo");
+            }
+            Err(e) => {
+                tracing::warn!(provider = %provider.name(), error = %e, "Search API failed, falling back to DuckDuckGo");
+            }
+        }
+    }
 
     // Try DuckDuckGo instant answer API first
     let ddg_url = format!(
@@ -124,6 +246,155 @@ pub async fn perform_google_search(query: &str) -> Result<String> {
               urlencoding::encode(query)))
 }
 
+/// Results requested from a search API
+const SEARCH_RESULT_COUNT: usize = 5;
+
+/// Web search backend, picked from the environment (or `.env`)
+///
+/// * `BRAVE_API_KEY` - Brave Search API
+/// * `GOOGLE_CSE_KEY` and `GOOGLE_CSE_ID` - Google Programmable Search (Custom Search JSON API)
+/// * neither - DuckDuckGo instant answers and HTML results, no key needed
+enum SearchProvider {
+    Brave { api_key: String },
+    GoogleCse { api_key: String, engine_id: String },
+    DuckDuckGo,
+}
+
+/// One structured search hit
+struct SearchResult {
+    title: String,
+    url: String,
+    snippet: String,
+}
+
+impl SearchProvider {
+    fn from_env() -> Self {
+        let var = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .filter(|value| !value.trim().is_empty())
+        };
+
+        if let Some(api_key) = var("BRAVE_API_KEY") {
+            return Self::Brave { api_key };
+        }
+        if let (Some(api_key), Some(engine_id)) = (var("GOOGLE_CSE_KEY"), var("GOOGLE_CSE_ID")) {
+            return Self::GoogleCse { api_key, engine_id };
+        }
+        Self::DuckDuckGo
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Brave { .. } => "Brave Search",
+            Self::GoogleCse { .. } => "Google Custom Search",
+            Self::DuckDuckGo => "DuckDuckGo",
+        }
+    }
+
+    /// Query a search API, the DuckDuckGo path is handled by `search_web` itself
+    ///
+    /// Request errors are reported without their URL, which may hold the API key.
+    async fn search(&self, client: &Client, query: &str) -> Result<Vec<SearchResult>> {
+        let request = match self {
+            Self::Brave { api_key } => client
+                .get("https://api.search.brave.com/res/v1/web/search")
+                .header("Accept", "application/json")
+                .header("X-Subscription-Token", api_key)
+                .query(&[
+                    ("q", query),
+                    ("count", &SEARCH_RESULT_COUNT.to_string()),
+                    ("search_lang", "de"),
+                ]),
+            Self::GoogleCse { api_key, engine_id } => client
+                .get("https://www.googleapis.com/customsearch/v1")
+                .query(&[
+                    ("key", api_key.as_str()),
+                    ("cx", engine_id),
+                    ("q", query),
+                    ("num", &SEARCH_RESULT_COUNT.to_string()),
+                    ("hl", "de"),
+                ]),
+            Self::DuckDuckGo => return Ok(Vec::new()),
+        };
+
+        let json: Value = request
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| anyhow::anyhow!("{} request failed: {}", self.name(), e.without_url()))?
+            .json()
+            .await
+            .map_err(|e| {
+                anyhow::anyhow!("Invalid {} response: {}", self.name(), e.without_url())
+            })?;
+
+        // Brave lists hits under web.results with a description, Google under items with a snippet
+        let (hits, url_field, snippet_field) = match self {
+            Self::Brave { .. } => (&json["web"]["results"], "url", "description"),
+            _ => (&json["items"], "link", "snippet"),
+        };
+
+        Ok(hits
+            .as_array()
+            .map(|hits| {
+                hits.iter()
+                    .take(SEARCH_RESULT_COUNT)
+                    .filter_map(|hit| {
+                        Some(SearchResult {
+                            title: strip_html_tags(hit["title"].as_str().unwrap_or_default()),
+                            url: hit[url_field].as_str()?.to_string(),
+                            snippet: strip_html_tags(
+                                hit[snippet_field].as_str().unwrap_or_default(),
+                            ),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+}
+
+/// Format search hits the way the model gets them from every provider
+fn format_search_results(results: &[SearchResult]) -> String {
+    results
+        .iter()
+        .map(|result| {
+            format!(
+                "Title: {}\nURL: {}\nSnippet: {}",
+                result.title, result.url, result.snippet
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n---\n\n")
+}
+
+/// Build the likely German Wikipedia article URL for a series name
+fn german_wikipedia_url(series: &str) -> String {
+    let title = series.split_whitespace().collect::<Vec<&str>>().join("_");
+    format!(
+        "https://de.wikipedia.org/wiki/{}",
+        urlencoding::encode(&title)
+    )
+}
+
+/// Read the German Wikipedia article of a series, returning its URL and content
+///
+/// Returns `None` if the article doesn't exist or can't be read, so callers can
+/// fall back to a general web search.
+async fn read_german_wikipedia_article(client: &Client, series: &str) -> Option<(String, String)> {
+    let url = german_wikipedia_url(series);
+    tracing::info!(series = %series, url = %url, "Trying German Wikipedia article directly");
+
+    match read_website_content(client, &url).await {
+        Ok(content) => Some((url, content)),
+        Err(e) => {
+            tracing::debug!(url = %url, error = %e, "No German Wikipedia article, falling back to search");
+            None
+        }
+    }
+}
+
 /// Scrape DuckDuckGo search results from HTML
 fn scrape_duckduckgo_results(html: &str) -> Result<String> {
     let document = Html::parse_document(html);
@@ -152,19 +423,18 @@ fn scrape_duckduckgo_results(html: &str) -> Result<String> {
             .and_then(|e| e.value().attr("href"))
             .unwrap_or_default();
 
-        results.push(format!(
-            "Title: {}\nURL: {}\nSnippet: {}",
-            strip_html_tags(&title),
-            url,
-            strip_html_tags(&snippet)
-        ));
+        results.push(SearchResult {
+            title: strip_html_tags(&title),
+            url: url.to_string(),
+            snippet: strip_html_tags(&snippet),
+        });
     }
 
     if results.is_empty() {
         tracing::warn!("DuckDuckGo scraping found no results");
         return Ok("No search results found".to_string());
     } else {
-        let result_summary = results.join("\n\n---\n\n");
+        let result_summary = format_search_results(&results);
         tracing::info!(
             result_count = %results.len(),
             total_length = %result_summary.len(),
@@ -174,51 +444,362 @@ fn scrape_duckduckgo_results(html: &str) -> Result<String> {
     }
 }
 
+/// Maximum number of linked episode-list subpages followed from a Wikipedia page
+const MAX_EPISODE_LIST_PAGES: usize = 3;
+
 /// Reads and extracts content from a website
-pub async fn read_website_content(url: &str) -> Result<String> {
+///
+/// For Wikipedia pages, links to separate episode lists ("Liste der ...-Episoden",
+/// "List of ... episodes") or per-season subpages are followed as well (bounded by
+/// `MAX_EPISODE_LIST_PAGES`) and their content is appended, so long-running series
+/// whose episodes are split across several pages are covered completely. Episode
+/// tables on those pages are passed on as structured JSON (see `extract_episode_tables`).
+pub async fn read_website_content(client: &Client, url: &str) -> Result<String> {
     tracing::info!(url = %url, "Starting website content extraction");
 
     // Validate URL
     let parsed_url = Url::parse(url).map_err(|_| anyhow::anyhow!("Invalid URL: {}", url))?;
-    
+
     tracing::debug!(validated_url = %parsed_url, "URL validation successful");
 
-    let client = Client::builder()
-        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")
-        .timeout(std::time::Duration::from_secs(30))
-        .build()?;
+    let html_content = fetch_html(client, url).await?;
 
-    let response = client.get(url).send().await?;
+    // Extract content using multiple selectors for different sites
+    let (mut content, episode_list_urls) = {
+        let document = Html::parse_document(&html_content);
+        let content = extract_page_content(&document, &parsed_url)?;
+        let links = if parsed_url
+            .host_str()
+            .unwrap_or("")
+            .contains("wikipedia.org")
+        {
+            find_episode_list_links(&document, &parsed_url)
+        } else {
+            Vec::new()
+        };
+        (content, links)
+    };
 
-    if !response.status().is_success() {
-        return Err(anyhow::anyhow!("HTTP error {}: {}", response.status(), url));
+    let mut followed_pages = 0;
+    for list_url in episode_list_urls.iter().take(MAX_EPISODE_LIST_PAGES) {
+        tracing::info!(url = %list_url, "Following linked episode list page");
+
+        let sub_content = match fetch_html(client, list_url.as_str()).await {
+            Ok(html) => extract_page_content(&Html::parse_document(&html), list_url),
+            Err(e) => Err(e),
+        };
+
+        match sub_content {
+            Ok(sub_content) => {
+                content.push_str(&format!(
+                    "\n\n=== Episode list: {} ===\n\n{}",
+                    list_url, sub_content
+                ));
+                followed_pages += 1;
+            }
+            Err(e) => {
+                tracing::warn!(url = %list_url, error = %e, "Failed to read episode list page");
+            }
+        }
     }
 
-    let html_content = response.text().await?;
-    let document = Html::parse_document(&html_content);
-
-    // Extract content using multiple selectors for different sites
-    let content = extract_main_content(&document, &parsed_url)?;
-
-    // Limit content size to avoid overwhelming the AI
+    // Limit content size to avoid overwhelming the AI (more room for combined pages)
     const MAX_LENGTH: usize = 8000;
-    if content.len() > MAX_LENGTH {
+    const MAX_COMBINED_LENGTH: usize = 16000;
+    let max_length = if followed_pages > 0 {
+        MAX_COMBINED_LENGTH
+    } else {
+        MAX_LENGTH
+    };
+
+    if content.len() > max_length {
         tracing::info!(
             original_length = %content.len(),
-            truncated_length = %MAX_LENGTH,
+            truncated_length = %max_length,
+            followed_pages = %followed_pages,
             "Content truncated due to size limit"
         );
+        let mut cut = max_length;
+        while !content.is_char_boundary(cut) {
+            cut -= 1;
+        }
         Ok(format!(
             "{}...\n\n[Content truncated to {} characters]",
-            &content[..MAX_LENGTH],
-            MAX_LENGTH
+            &content[..cut],
+            max_length
         ))
     } else {
-        tracing::info!(content_length = %content.len(), "Content extraction successful");
+        tracing::info!(
+            content_length = %content.len(),
+            followed_pages = %followed_pages,
+            "Content extraction successful"
+        );
         Ok(content)
     }
 }
 
+/// Fetch a page and return its HTML body
+async fn fetch_html(client: &Client, url: &str) -> Result<String> {
+    let response = client.get(url).send().await?;
+
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!("HTTP error {}: {}", response.status(), url));
+    }
+
+    Ok(response.text().await?)
+}
+
+/// Find links from a Wikipedia article to separate episode-list or season pages
+/// of the same series
+fn find_episode_list_links(document: &Html, url: &Url) -> Vec<Url> {
+    let link_selector = Selector::parse("div.mw-parser-output a[href^='/wiki/']").unwrap();
+
+    // Significant words of the current article title, used to make sure a
+    // linked list page belongs to the same series
+    let page_title = url
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .map(|segment| {
+            urlencoding::decode(segment)
+                .replace('_', " ")
+                .to_lowercase()
+        })
+        .unwrap_or_default();
+    let series_words: Vec<&str> = page_title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| {
+            word.len() >= 4
+                && !matches!(*word, "liste" | "list" | "episoden" | "episodes" | "folgen")
+        })
+        .collect();
+
+    let mut links: Vec<Url> = Vec::new();
+
+    for element in document.select(&link_selector) {
+        let Some(href) = element.value().attr("href") else {
+            continue;
+        };
+        let target = urlencoding::decode(href.trim_start_matches("/wiki/"))
+            .replace('_', " ")
+            .to_lowercase();
+
+        // Skip namespaced pages (Datei:, Kategorie:, Special:, ...)
+        if target.contains(':') {
+            continue;
+        }
+
+        let is_episode_list = target.contains("episode")
+            || target.contains("folgen")
+            || target.contains("staffel")
+            || target.contains("season");
+        let same_series =
+            series_words.is_empty() || series_words.iter().any(|word| target.contains(word));
+
+        if !is_episode_list || !same_series {
+            continue;
+        }
+
+        if let Ok(mut link) = url.join(href) {
+            link.set_fragment(None);
+            if link.path() != url.path() && !links.contains(&link) {
+                links.push(link);
+            }
+        }
+    }
+
+    tracing::debug!(
+        link_count = %links.len(),
+        links = ?links.iter().map(|l| l.as_str()).collect::<Vec<_>>(),
+        "Detected linked episode list pages"
+    );
+
+    links
+}
+
+/// Extract the text handed to the AI, structured episode tables for Wikipedia
+/// pages that have them and the main content otherwise
+fn extract_page_content(document: &Html, url: &Url) -> Result<String> {
+    if url.host_str().unwrap_or("").contains("wikipedia.org") {
+        let episodes = extract_episode_tables(document);
+        if !episodes.is_empty() {
+            tracing::info!(episode_count = %episodes.len(), "Extracted structured episode tables");
+
+            // One object per line, so a truncated list still reads cleanly
+            let rows = episodes
+                .iter()
+                .filter_map(|episode| serde_json::to_string(episode).ok())
+                .collect::<Vec<_>>()
+                .join(",\n");
+            return Ok(format!(
+                "Episode list from the page's episode tables (JSON, in page order):\n[\n{}\n]",
+                rows
+            ));
+        }
+        tracing::debug!("No episode tables detected, extracting page text");
+    }
+
+    extract_main_content(document, url)
+}
+
+/// One row of a Wikipedia episode table
+#[derive(Debug, Serialize)]
+struct EpisodeRow {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    episode_no: Option<String>,
+    title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    air_date: Option<String>,
+}
+
+/// Parse the `table.wikitable` episode tables of a Wikipedia page
+///
+/// A table counts as an episode table when its header row has a title column
+/// plus an episode number or air date column, in German ("Nr. (ges.)", "Titel",
+/// "Erstausstrahlung") or English ("No. overall", "Title", "Original air date").
+/// Other tables and rows that don't fill the detected columns (like the summary
+/// rows below each episode on English pages) are skipped.
+fn extract_episode_tables(document: &Html) -> Vec<EpisodeRow> {
+    let table_selector = Selector::parse("table.wikitable").unwrap();
+    let row_selector = Selector::parse("tr").unwrap();
+
+    let mut episodes = Vec::new();
+    for table in document.select(&table_selector) {
+        let mut rows = table.select(&row_selector);
+        let Some(columns) = rows
+            .by_ref()
+            .find(|row| {
+                !row.children()
+                    .filter_map(ElementRef::wrap)
+                    .any(|cell| cell.value().name() == "td")
+            })
+            .and_then(|header| EpisodeColumns::detect(&row_cells(header)))
+        else {
+            continue;
+        };
+
+        let table_start = episodes.len();
+        for row in rows {
+            // A single cell spanning the table is a summary or a season divider
+            if row.children().filter_map(ElementRef::wrap).count() < 2 {
+                continue;
+            }
+            let cells = row_cells(row);
+            let cell = |index: Option<usize>| {
+                index
+                    .and_then(|index| cells.get(index))
+                    .filter(|text| !text.is_empty())
+                    .cloned()
+            };
+            if cells.len() <= columns.max_index() {
+                continue;
+            }
+            let Some(title) = cell(Some(columns.title)) else {
+                continue;
+            };
+
+            episodes.push(EpisodeRow {
+                episode_no: cell(columns.episode_no),
+                title: title
+                    .trim_matches(|c| matches!(c, '"' | '„' | '“' | '”'))
+                    .to_string(),
+                air_date: cell(columns.air_date),
+            });
+        }
+
+        tracing::debug!(
+            columns = ?columns,
+            rows = %(episodes.len() - table_start),
+            "Parsed episode table"
+        );
+    }
+
+    episodes
+}
+
+/// Column positions of an episode table
+#[derive(Debug)]
+struct EpisodeColumns {
+    episode_no: Option<usize>,
+    title: usize,
+    air_date: Option<usize>,
+}
+
+impl EpisodeColumns {
+    /// Find the columns from the header cells, `None` for tables that aren't episode lists
+    fn detect(headers: &[String]) -> Option<Self> {
+        let headers: Vec<String> = headers.iter().map(|header| header.to_lowercase()).collect();
+        let find =
+            |matches: &dyn Fn(&str) -> bool| headers.iter().position(|header| matches(header));
+
+        // Prefer the overall number over the one within the season
+        let episode_no = find(&|h| h.contains("ges") || h.contains("overall"))
+            .filter(|&index| is_number_header(&headers[index]))
+            .or_else(|| find(&|h| is_number_header(h)));
+        // Prefer the German title over the original one on German pages
+        let title = find(&|h| h.contains("deutscher titel"))
+            .or_else(|| {
+                find(&|h| (h.contains("titel") || h.contains("title")) && !h.contains("original"))
+            })
+            .or_else(|| find(&|h| h.contains("titel") || h.contains("title")))?;
+        let air_date = find(&|h| {
+            h.contains("erstausstrahlung")
+                || h.contains("ausstrahlung")
+                || h.contains("air date")
+                || h.contains("airdate")
+                || h.contains("release date")
+        });
+
+        if episode_no.is_none() && air_date.is_none() {
+            return None;
+        }
+
+        Some(Self {
+            episode_no,
+            title,
+            air_date,
+        })
+    }
+
+    fn max_index(&self) -> usize {
+        [self.episode_no, Some(self.title), self.air_date]
+            .into_iter()
+            .flatten()
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Whether a (lowercase) header names an episode number column
+fn is_number_header(header: &str) -> bool {
+    header.starts_with("nr")
+        || header.starts_with("no.")
+        || header.starts_with('#')
+        || header == "folge"
+        || header == "episode"
+}
+
+/// Cleaned text of a table row's cells, repeated for cells spanning several columns
+fn row_cells(row: ElementRef) -> Vec<String> {
+    let footnote = regex::Regex::new(r"\[[^\]]{1,4}\]").unwrap();
+
+    let mut cells = Vec::new();
+    for cell in row
+        .children()
+        .filter_map(ElementRef::wrap)
+        .filter(|cell| matches!(cell.value().name(), "th" | "td"))
+    {
+        let text = clean_text(&footnote.replace_all(&cell.text().collect::<String>(), ""));
+        let span = cell
+            .value()
+            .attr("colspan")
+            .and_then(|span| span.parse::<usize>().ok())
+            .unwrap_or(1)
+            .clamp(1, 20);
+        cells.extend(std::iter::repeat_n(text, span));
+    }
+    cells
+}
+
 /// Extract main content from HTML document based on the website
 fn extract_main_content(document: &Html, url: &Url) -> Result<String> {
     let host = url.host_str().unwrap_or("");
@@ -397,4 +978,10 @@ mod urlencoding {
     pub fn encode(input: &str) -> String {
         url::form_urlencoded::byte_serialize(input.as_bytes()).collect()
     }
+
+    pub fn decode(input: &str) -> String {
+        ::urlencoding::decode(input)
+            .map(|decoded| decoded.into_owned())
+            .unwrap_or_else(|_| input.to_string())
+    }
 }
@@ -0,0 +1,141 @@
+//! On-disk cache for search responses
+//!
+//! Opt-in with `--cache-ttl <SECONDS>` (or `cache_ttl` in the config file).
+//! Each API request is stored as a JSON file under the OS cache directory
+//! (`~/.cache/mwb/search` on Linux), named by a hash of the query and the
+//! parameters sent to the API. A repeated search within the TTL reads the file
+//! instead of hitting the network, which makes iterating on client-side
+//! filters like `-i`/`-e` against the same base query instant.
+//!
+//! The cache only saves time, so failing to read or write it is never an
+//! error: broken or outdated files are treated as a miss.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Bumped whenever the stored layout or the key changes, older files are ignored
+const CACHE_VERSION: u32 = 1;
+
+/// Search response cache with a fixed time to live
+pub struct SearchCache {
+    dir: PathBuf,
+    ttl_secs: u64,
+}
+
+/// A cached API response
+#[derive(Serialize, Deserialize)]
+pub struct CachedSearch {
+    version: u32,
+    /// Unix timestamp of the request
+    created: i64,
+    pub results: Vec<mediathekviewweb::models::Item>,
+    total_results: u64,
+    filmliste_timestamp: i64,
+}
+
+impl CachedSearch {
+    /// Seconds since the response was fetched
+    pub fn age_secs(&self) -> i64 {
+        chrono::Utc::now().timestamp() - self.created
+    }
+
+    /// Split into the results and a `QueryInfo` like the API's
+    pub fn into_parts(
+        self,
+    ) -> (
+        Vec<mediathekviewweb::models::Item>,
+        mediathekviewweb::models::QueryInfo,
+    ) {
+        let query_info = mediathekviewweb::models::QueryInfo {
+            filmliste_timestamp: self.filmliste_timestamp,
+            result_count: self.results.len(),
+            search_engine_time: std::time::Duration::from_millis(0),
+            total_results: self.total_results,
+        };
+        (self.results, query_info)
+    }
+}
+
+impl SearchCache {
+    /// Open the cache, `None` when caching is off (`ttl_secs` of `None` or 0)
+    /// or the platform has no cache directory
+    pub fn open(ttl_secs: Option<u64>) -> Option<Self> {
+        let ttl_secs = ttl_secs.filter(|&ttl| ttl > 0)?;
+        let Some(base) = dirs::cache_dir() else {
+            tracing::warn!("Could not determine cache directory, caching disabled");
+            return None;
+        };
+
+        Some(Self {
+            dir: base.join("mwb").join("search"),
+            ttl_secs,
+        })
+    }
+
+    /// Cache key for a request, `parts` being everything sent to the API
+    pub fn key(parts: &[&str]) -> String {
+        // FNV-1a, stable across Rust versions unlike `DefaultHasher`
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for byte in format!("v{CACHE_VERSION}\u{1f}{}", parts.join("\u{1f}")).bytes() {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+        format!("{hash:016x}")
+    }
+
+    /// Read a cached response younger than the TTL
+    pub fn load(&self, key: &str) -> Option<CachedSearch> {
+        let path = self.dir.join(format!("{key}.json"));
+        let content = std::fs::read_to_string(&path).ok()?;
+
+        let cached: CachedSearch = match serde_json::from_str(&content) {
+            Ok(cached) => cached,
+            Err(e) => {
+                tracing::debug!(path = %path.display(), error = %e, "Ignoring unreadable cache file");
+                return None;
+            }
+        };
+
+        if cached.version != CACHE_VERSION {
+            return None;
+        }
+
+        let age = cached.age_secs();
+        if age < 0 || age as u64 >= self.ttl_secs {
+            tracing::debug!(path = %path.display(), age_secs = %age, ttl_secs = %self.ttl_secs, "Cache entry expired");
+            return None;
+        }
+
+        tracing::info!(path = %path.display(), age_secs = %age, results = %cached.results.len(), "Cache hit");
+        Some(cached)
+    }
+
+    /// Store a response, failures are only logged
+    pub fn store(
+        &self,
+        key: &str,
+        results: &[mediathekviewweb::models::Item],
+        query_info: &mediathekviewweb::models::QueryInfo,
+    ) {
+        let path = self.dir.join(format!("{key}.json"));
+        let cached = CachedSearch {
+            version: CACHE_VERSION,
+            created: chrono::Utc::now().timestamp(),
+            results: results.to_vec(),
+            total_results: query_info.total_results,
+            filmliste_timestamp: query_info.filmliste_timestamp,
+        };
+
+        let written = std::fs::create_dir_all(&self.dir)
+            .map_err(anyhow::Error::from)
+            .and_then(|()| Ok(serde_json::to_string(&cached)?))
+            .and_then(|json| Ok(std::fs::write(&path, json)?));
+
+        match written {
+            Ok(()) => tracing::debug!(path = %path.display(), "Stored search in cache"),
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "Failed to write cache file")
+            }
+        }
+    }
+}
@@ -0,0 +1,131 @@
+//! Configuration file support
+//!
+//! Loads user defaults from a TOML file so frequently used options don't have
+//! to be repeated on every invocation. The file lives at
+//! `~/.config/mwb/config.toml` (or `%APPDATA%\mwb\config.toml` on Windows) and
+//! is entirely optional - a missing file yields the built-in defaults.
+//!
+//! Search option defaults (`size`, `offset`, `sort_by`, `sort_order`, `format`,
+//! `exclude_future`, `cache_ttl`) only apply when the matching flag isn't given
+//! on the command line.
+//!
+//! Example:
+//! ```toml
+//! default_channels = ["ARD", "ZDF", "Arte"]
+//! ai_fields = ["title", "topic", "duration", "channel", "url", "date"]
+//! size = 30
+//! format = "oneline"
+//! exclude_future = true
+//! ```
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::PathBuf;
+
+/// User configuration read from `config.toml`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Channels every search is implicitly restricted to when the query
+    /// contains no explicit `!channel` selector (override with `--all-channels`)
+    pub default_channels: Vec<String>,
+
+    /// Per-episode fields sent to the AI (see `ai::EPISODE_FIELDS`),
+    /// defaults to `ai::DEFAULT_EPISODE_FIELDS`
+    pub ai_fields: Option<Vec<String>>,
+
+    /// Default for `--size`
+    pub size: Option<u32>,
+
+    /// Default for `--offset`
+    pub offset: Option<u32>,
+
+    /// Default for `--sort-by` (timestamp, duration, channel, title, topic)
+    pub sort_by: Option<String>,
+
+    /// Default for `--sort-order` (asc, desc)
+    pub sort_order: Option<String>,
+
+    /// Default for `--format`
+    pub format: Option<String>,
+
+    /// Default for `--no-future`, `--future` overrides it for a single search
+    pub exclude_future: Option<bool>,
+
+    /// Default for `--cache-ttl` in seconds, turns the response cache on for every search
+    pub cache_ttl: Option<u64>,
+}
+
+impl Config {
+    /// Load the configuration file, falling back to defaults when it doesn't exist
+    pub fn load() -> Result<Self> {
+        let Some(path) = config_path() else {
+            tracing::debug!("Could not determine config directory, using defaults");
+            return Ok(Self::default());
+        };
+
+        if !path.exists() {
+            tracing::debug!(path = %path.display(), "No config file found, using defaults");
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        let config: Config = toml::from_str(&content)
+            .with_context(|| format!("Invalid config file {}", path.display()))?;
+
+        config
+            .validate()
+            .with_context(|| format!("Invalid config file {}", path.display()))?;
+
+        tracing::info!(path = %path.display(), config = ?config, "Loaded config file");
+
+        Ok(config)
+    }
+
+    /// Check values that serde can't validate on its own
+    fn validate(&self) -> Result<()> {
+        if let Some(fields) = &self.ai_fields {
+            for field in fields {
+                if !crate::ai::EPISODE_FIELDS.contains(&field.as_str()) {
+                    anyhow::bail!(
+                        "Unknown ai_fields entry '{}'. Valid fields: {}",
+                        field,
+                        crate::ai::EPISODE_FIELDS.join(", ")
+                    );
+                }
+            }
+        }
+
+        if let Some(sort_by) = &self.sort_by {
+            if !["timestamp", "duration", "channel", "title", "topic"].contains(&sort_by.as_str()) {
+                anyhow::bail!(
+                    "Unknown sort_by '{}'. Valid values: timestamp, duration, channel, title, topic",
+                    sort_by
+                );
+            }
+        }
+
+        if let Some(sort_order) = &self.sort_order {
+            if !["asc", "desc"].contains(&sort_order.as_str()) {
+                anyhow::bail!(
+                    "Unknown sort_order '{}'. Valid values: asc, desc",
+                    sort_order
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Resolve the location of the config file for the current platform
+pub fn config_path() -> Option<PathBuf> {
+    let base = if cfg!(target_os = "windows") {
+        dirs::config_dir()
+    } else {
+        dirs::home_dir().map(|home| home.join(".config"))
+    };
+
+    base.map(|dir| dir.join("mwb").join("config.toml"))
+}
@@ -0,0 +1,473 @@
+//! Downloading videos
+//!
+//! `mwb download` runs a search like `mwb search` and streams each result's
+//! video (in the `--quality` chosen) into a directory. Files are written to a
+//! `.part` file first and renamed once complete, so an interrupted download is
+//! never mistaken for a finished one and skipped on the next run.
+//!
+//! On a terminal each file gets a progress bar above an overall one for the
+//! batch; redirected output gets one line per file instead.
+//!
+//! Finished downloads are recorded in a manifest (`~/.local/share/mwb/downloads.json`
+//! on Linux) with their URL, local path and size. A video whose file is still
+//! there at that size is skipped, whatever the output directory of the new
+//! run. `--force` ignores the manifest and downloads everything again.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use indicatif::{HumanBytes, MultiProgress, ProgressBar, ProgressStyle};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncWriteExt;
+
+use crate::http::HttpOptions;
+use crate::{sanitize_filename_component, select_video_url, video_extension, USER_AGENT};
+
+/// Where and how `mwb download` stores the videos
+#[derive(Debug, Clone)]
+pub struct DownloadOptions {
+    pub output_dir: PathBuf,
+    /// Replace existing files instead of skipping them
+    pub overwrite: bool,
+    /// Download again even when the manifest or an existing file says it's done
+    pub force: bool,
+    /// Extra passes over the downloads that failed
+    pub retries: u32,
+    /// Proxy and timeouts from `--proxy` and `--timeout`
+    pub http: HttpOptions,
+}
+
+/// A video queued for download
+struct Download<'a> {
+    url: &'a str,
+    path: PathBuf,
+}
+
+/// Download the videos of all results into `options.output_dir`
+///
+/// Failed downloads are retried `options.retries` times after the first pass.
+/// Returns an error listing the files that still failed after that.
+pub async fn download_videos(
+    results: &[mediathekviewweb::models::Item],
+    quality: &str,
+    options: &DownloadOptions,
+) -> Result<()> {
+    if results.is_empty() {
+        println!("{}", "No results found to download.".yellow());
+        return Ok(());
+    }
+
+    std::fs::create_dir_all(&options.output_dir).with_context(|| {
+        format!(
+            "Failed to create output directory {}",
+            options.output_dir.display()
+        )
+    })?;
+
+    let client = options
+        .http
+        .download_client_builder()
+        .user_agent(USER_AGENT)
+        .build()?;
+
+    let mut manifest = Manifest::load();
+    let mut used_names = HashSet::new();
+    let mut pending = Vec::new();
+    let mut skipped = 0;
+    for entry in results {
+        let url = select_video_url(entry, quality);
+        let filename = unique_filename(&download_filename(entry, url), &mut used_names);
+        let path = options.output_dir.join(filename);
+
+        if let Some(existing) = manifest.existing(url).filter(|_| !options.force) {
+            println!(
+                "{}",
+                format!(
+                    "⏭️  Skipping {} (already downloaded to {})",
+                    path.display(),
+                    existing.display()
+                )
+                .yellow()
+            );
+            skipped += 1;
+            continue;
+        }
+
+        if path.exists() && !options.overwrite && !options.force {
+            println!(
+                "{}",
+                format!("⏭️  Skipping {} (already exists)", path.display()).yellow()
+            );
+            skipped += 1;
+            continue;
+        }
+
+        pending.push(Download { url, path });
+    }
+
+    let total = pending.len();
+    let mut failed = download_all(&client, pending, &mut manifest).await;
+    let mut attempt = 0;
+    while !failed.is_empty() && attempt < options.retries {
+        attempt += 1;
+        println!(
+            "{}",
+            format!(
+                "🔁 Retrying {} failed download(s) (attempt {}/{})",
+                failed.len(),
+                attempt,
+                options.retries
+            )
+            .yellow()
+        );
+        failed = download_all(&client, failed, &mut manifest).await;
+    }
+
+    println!(
+        "{}",
+        format!(
+            "Downloaded {} video(s), skipped {}, failed {}",
+            total - failed.len(),
+            skipped,
+            failed.len()
+        )
+        .green()
+    );
+
+    if !failed.is_empty() {
+        let paths = failed
+            .iter()
+            .map(|download| format!("  {} ({})", download.path.display(), download.url))
+            .collect::<Vec<_>>()
+            .join("\n");
+        anyhow::bail!("{} download(s) failed:\n{}", failed.len(), paths);
+    }
+
+    Ok(())
+}
+
+/// Bumped whenever the manifest layout changes, older manifests are ignored
+const MANIFEST_VERSION: u32 = 1;
+
+/// Videos downloaded in earlier runs, by URL
+///
+/// Like the search cache, the manifest only saves work: failing to read or
+/// write it is logged, never an error.
+#[derive(Serialize, Deserialize)]
+struct Manifest {
+    version: u32,
+    entries: BTreeMap<String, ManifestEntry>,
+    /// Where the manifest is stored, `None` when the platform has no data directory
+    #[serde(skip)]
+    file: Option<PathBuf>,
+}
+
+/// A finished download
+#[derive(Serialize, Deserialize)]
+struct ManifestEntry {
+    /// Absolute path of the video
+    path: PathBuf,
+    /// Bytes written
+    size: u64,
+    /// Unix timestamp of the download
+    downloaded: i64,
+}
+
+impl Manifest {
+    /// Read the manifest, starting an empty one when there is none yet
+    fn load() -> Self {
+        let file = dirs::data_dir().map(|dir| dir.join("mwb").join("downloads.json"));
+        let mut manifest = Self {
+            version: MANIFEST_VERSION,
+            entries: BTreeMap::new(),
+            file: None,
+        };
+
+        if let Some(path) = &file {
+            match std::fs::read_to_string(path) {
+                Ok(content) => match serde_json::from_str::<Self>(&content) {
+                    Ok(stored) if stored.version == MANIFEST_VERSION => {
+                        tracing::debug!(path = %path.display(), entries = %stored.entries.len(), "Loaded download manifest");
+                        manifest = stored;
+                    }
+                    Ok(_) => {
+                        tracing::debug!(path = %path.display(), "Ignoring download manifest of another version")
+                    }
+                    Err(e) => {
+                        tracing::warn!(path = %path.display(), error = %e, "Ignoring unreadable download manifest")
+                    }
+                },
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => {
+                    tracing::warn!(path = %path.display(), error = %e, "Failed to read download manifest")
+                }
+            }
+        } else {
+            tracing::warn!("Could not determine data directory, download manifest disabled");
+        }
+
+        manifest.file = file;
+        manifest
+    }
+
+    /// Where the video from `url` was saved, if the file is still there at its recorded size
+    fn existing(&self, url: &str) -> Option<&Path> {
+        let entry = self.entries.get(url)?;
+        match std::fs::metadata(&entry.path) {
+            Ok(metadata) if metadata.len() == entry.size => Some(&entry.path),
+            Ok(metadata) => {
+                tracing::info!(url = %url, path = %entry.path.display(), expected = %entry.size, actual = %metadata.len(), "Downloaded file changed size, downloading again");
+                None
+            }
+            Err(_) => {
+                tracing::info!(url = %url, path = %entry.path.display(), "Downloaded file is gone, downloading again");
+                None
+            }
+        }
+    }
+
+    /// Record a finished download and save the manifest right away, so an
+    /// interrupted batch keeps what it finished
+    fn record(&mut self, url: &str, path: &Path, size: u64) {
+        let path = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+        self.entries.insert(
+            url.to_string(),
+            ManifestEntry {
+                path,
+                size,
+                downloaded: chrono::Utc::now().timestamp(),
+            },
+        );
+
+        let Some(file) = &self.file else {
+            return;
+        };
+        let written = file
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .map_err(anyhow::Error::from)
+            .and_then(|()| Ok(serde_json::to_string_pretty(self)?))
+            .and_then(|json| Ok(std::fs::write(file, json)?));
+        if let Err(e) = written {
+            tracing::warn!(path = %file.display(), error = %e, "Failed to write download manifest");
+        }
+    }
+}
+
+/// Progress bars for a batch, only shown when stdout is a terminal
+struct Progress {
+    multi: MultiProgress,
+    /// Files done out of the batch
+    overall: ProgressBar,
+}
+
+impl Progress {
+    fn new(total: usize) -> Option<Self> {
+        if !std::io::stdout().is_terminal() {
+            return None;
+        }
+
+        let multi = MultiProgress::new();
+        let overall = multi.add(ProgressBar::new(total as u64));
+        overall.set_style(
+            ProgressStyle::with_template("{prefix:.bold} [{bar:30.green/white}] {pos}/{len} files")
+                .expect("valid progress template")
+                .progress_chars("=> "),
+        );
+        overall.set_prefix("Total");
+
+        Some(Self { multi, overall })
+    }
+
+    /// A bar for one file above the overall bar, sized once the response arrives
+    fn file_bar(&self, name: &str) -> ProgressBar {
+        let bar = self
+            .multi
+            .insert_before(&self.overall, ProgressBar::no_length());
+        bar.set_message(name.to_string());
+        bar
+    }
+
+    /// Print a line above the bars without tearing them
+    fn println(&self, line: &str) {
+        let _ = self.multi.println(line);
+    }
+}
+
+/// Print a line above the progress bars, or plainly without them
+fn report(progress: Option<&Progress>, line: &str) {
+    match progress {
+        Some(progress) => progress.println(line),
+        None => println!("{line}"),
+    }
+}
+
+/// Download one after the other, recording finished ones in the manifest and
+/// returning the ones that failed
+async fn download_all<'a>(
+    client: &reqwest::Client,
+    downloads: Vec<Download<'a>>,
+    manifest: &mut Manifest,
+) -> Vec<Download<'a>> {
+    let total = downloads.len();
+    let progress = Progress::new(total);
+    let mut failed = Vec::new();
+
+    for (index, download) in downloads.into_iter().enumerate() {
+        let name = download
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let bar = progress.as_ref().map(|progress| progress.file_bar(&name));
+        if bar.is_none() {
+            println!(
+                "⬇️  [{}/{}] {}",
+                index + 1,
+                total,
+                download.path.display().to_string().cyan()
+            );
+        }
+
+        let result = download_file(client, download.url, &download.path, bar.as_ref()).await;
+        if let Some(bar) = &bar {
+            bar.finish_and_clear();
+        }
+
+        match result {
+            Ok(bytes) => {
+                tracing::info!(url = %download.url, path = %download.path.display(), bytes = %bytes, "Download finished");
+                manifest.record(download.url, &download.path, bytes);
+                report(
+                    progress.as_ref(),
+                    &format!("✅ {} ({})", name, HumanBytes(bytes)),
+                );
+            }
+            Err(e) => {
+                report(
+                    progress.as_ref(),
+                    &format!("{}", format!("❌ {:#}", e).red()),
+                );
+                failed.push(download);
+            }
+        }
+
+        if let Some(progress) = &progress {
+            progress.overall.inc(1);
+        }
+    }
+
+    if let Some(progress) = &progress {
+        progress.overall.finish_and_clear();
+    }
+
+    failed
+}
+
+/// Stream a video to `path` in chunks, returning the number of bytes written
+///
+/// `bar` follows the streamed bytes against `Content-Length`, or spins when
+/// the server doesn't send one.
+async fn download_file(
+    client: &reqwest::Client,
+    url: &str,
+    path: &Path,
+    bar: Option<&ProgressBar>,
+) -> Result<u64> {
+    let mut response = client
+        .get(url)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .with_context(|| format!("Failed to download {url}"))?;
+
+    if let Some(bar) = bar {
+        match response.content_length() {
+            Some(length) => {
+                bar.set_length(length);
+                bar.set_style(
+                    ProgressStyle::with_template(
+                        "{msg} [{bar:30.cyan/blue}] {bytes}/{total_bytes} {bytes_per_sec} {eta}",
+                    )
+                    .expect("valid progress template")
+                    .progress_chars("=> "),
+                );
+            }
+            None => {
+                bar.set_style(
+                    ProgressStyle::with_template("{spinner} {msg} {bytes} {bytes_per_sec}")
+                        .expect("valid progress template"),
+                );
+                bar.enable_steady_tick(std::time::Duration::from_millis(120));
+            }
+        }
+    }
+
+    let part_path = path.with_extension(format!(
+        "{}.part",
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or_default()
+    ));
+    let mut file = tokio::fs::File::create(&part_path)
+        .await
+        .with_context(|| format!("Failed to create {}", part_path.display()))?;
+
+    let mut bytes = 0u64;
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .with_context(|| format!("Download of {url} was interrupted"))?
+    {
+        file.write_all(&chunk)
+            .await
+            .with_context(|| format!("Failed to write {}", part_path.display()))?;
+        bytes += chunk.len() as u64;
+        if let Some(bar) = bar {
+            bar.inc(chunk.len() as u64);
+        }
+    }
+    file.flush().await?;
+    drop(file);
+
+    tokio::fs::rename(&part_path, path)
+        .await
+        .with_context(|| format!("Failed to move {} into place", part_path.display()))?;
+
+    Ok(bytes)
+}
+
+/// Filename for a downloaded video: `Channel_Topic_Title.ext`, extension from the chosen URL
+fn download_filename(entry: &mediathekviewweb::models::Item, url: &str) -> String {
+    let stem = sanitize_filename_component(&format!(
+        "{} {} {}",
+        entry.channel, entry.topic, entry.title
+    ));
+    let extension = video_extension(url);
+    if stem.is_empty() {
+        format!("mwb_video.{extension}")
+    } else {
+        format!("{stem}.{extension}")
+    }
+}
+
+/// Number repeated filenames within one batch (`name_2.mp4`, `name_3.mp4`, ...)
+fn unique_filename(filename: &str, used: &mut HashSet<String>) -> String {
+    if used.insert(filename.to_string()) {
+        return filename.to_string();
+    }
+
+    let (stem, extension) = filename.rsplit_once('.').unwrap_or((filename, ""));
+    (2..)
+        .map(|n| {
+            if extension.is_empty() {
+                format!("{stem}_{n}")
+            } else {
+                format!("{stem}_{n}.{extension}")
+            }
+        })
+        .find(|candidate| used.insert(candidate.clone()))
+        .expect("unbounded range always yields a free name")
+}
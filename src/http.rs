@@ -0,0 +1,77 @@
+//! Proxy and timeout settings for the HTTP clients mwb builds itself
+//!
+//! `--proxy` and `--timeout` are applied to the clients for downloads, URL
+//! checks and the AI requests. The MediathekViewWeb client is created by the
+//! `mediathekviewweb` crate, which offers no way to configure it: it only
+//! follows `HTTPS_PROXY` from the environment, and its requests are timed out
+//! by `send_api_request` instead.
+
+use anyhow::Result;
+use reqwest::{Client, ClientBuilder, Proxy};
+use std::time::Duration;
+
+/// Settings from `--proxy` and `--timeout`
+#[derive(Debug, Clone, Default)]
+pub struct HttpOptions {
+    /// Proxy every request goes through, `None` to follow the environment
+    pub proxy: Option<Proxy>,
+    /// Limit from `--timeout`, replacing each client's own default
+    pub timeout: Option<Duration>,
+}
+
+impl HttpOptions {
+    /// Check the `--proxy` URL and turn `--timeout` seconds into a duration
+    pub fn new(proxy: Option<&str>, timeout_secs: Option<u64>) -> Result<Self> {
+        let proxy = match proxy {
+            Some(proxy) => {
+                let url = url::Url::parse(proxy)
+                    .map_err(|e| anyhow::anyhow!("Invalid --proxy URL '{}': {}", proxy, e))?;
+                if !matches!(url.scheme(), "http" | "https") {
+                    anyhow::bail!(
+                        "Unsupported --proxy scheme '{}', use an http:// or https:// proxy URL",
+                        url.scheme()
+                    );
+                }
+                // The URL may carry credentials, only the host is logged
+                tracing::info!(proxy_host = %url.host_str().unwrap_or_default(), "Using proxy");
+                Some(
+                    Proxy::all(url)
+                        .map_err(|e| anyhow::anyhow!("Invalid --proxy URL '{}': {}", proxy, e))?,
+                )
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            proxy,
+            timeout: timeout_secs.map(Duration::from_secs),
+        })
+    }
+
+    /// A client builder using the proxy, with requests limited to `--timeout`
+    /// or `default_timeout`
+    pub fn client_builder(&self, default_timeout: Duration) -> ClientBuilder {
+        self.proxied_builder()
+            .timeout(self.timeout.unwrap_or(default_timeout))
+    }
+
+    /// A client builder for video downloads
+    ///
+    /// A whole video takes far longer than any sensible request limit, so
+    /// `--timeout` only bounds connecting and each wait for more data.
+    pub fn download_client_builder(&self) -> ClientBuilder {
+        let builder = self.proxied_builder();
+        match self.timeout {
+            Some(timeout) => builder.connect_timeout(timeout).read_timeout(timeout),
+            None => builder,
+        }
+    }
+
+    fn proxied_builder(&self) -> ClientBuilder {
+        let builder = Client::builder();
+        match &self.proxy {
+            Some(proxy) => builder.proxy(proxy.clone()),
+            None => builder,
+        }
+    }
+}
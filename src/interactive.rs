@@ -0,0 +1,183 @@
+//! Picking results by hand with `--interactive`
+//!
+//! After filtering, the results are shown as a scrollable list in the
+//! terminal. Space marks entries, Enter continues with the marked ones (or the
+//! highlighted one when nothing is marked), Esc or `q` cancels. Without another
+//! action like `--download` or `--play-first`, the picks become a playlist that
+//! is opened in the player.
+
+use anyhow::Result;
+use chrono::DateTime;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    DefaultTerminal, Frame,
+};
+use std::io::IsTerminal;
+
+/// Rows moved by PageUp/PageDown
+const PAGE_SIZE: usize = 10;
+
+/// What the user did in the list
+enum Outcome {
+    /// Indices of the chosen results, in result order
+    Picked(Vec<usize>),
+    Cancelled,
+}
+
+struct Picker<'a> {
+    results: &'a [mediathekviewweb::models::Item],
+    marked: Vec<bool>,
+    state: ListState,
+}
+
+/// Let the user pick results, `None` when the selection was cancelled
+pub fn pick_results(
+    results: &[mediathekviewweb::models::Item],
+) -> Result<Option<Vec<mediathekviewweb::models::Item>>> {
+    if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+        anyhow::bail!("--interactive needs a terminal, stdin or stdout is redirected");
+    }
+
+    let mut picker = Picker {
+        results,
+        marked: vec![false; results.len()],
+        state: ListState::default().with_selected(Some(0)),
+    };
+
+    let mut terminal = ratatui::try_init()?;
+    let outcome = picker.run(&mut terminal);
+    ratatui::try_restore()?;
+
+    match outcome? {
+        Outcome::Picked(indices) => {
+            tracing::info!(
+                picked = %indices.len(),
+                total = %results.len(),
+                "Results picked interactively"
+            );
+            Ok(Some(
+                indices.into_iter().map(|i| results[i].clone()).collect(),
+            ))
+        }
+        Outcome::Cancelled => {
+            tracing::info!("Interactive selection cancelled");
+            Ok(None)
+        }
+    }
+}
+
+impl Picker<'_> {
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<Outcome> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            // Windows also reports key releases
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => return Ok(Outcome::Cancelled),
+                // Raw mode swallows the signal, so Ctrl+C arrives as a key
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(Outcome::Cancelled)
+                }
+                KeyCode::Enter => return Ok(Outcome::Picked(self.picked())),
+                KeyCode::Char(' ') => {
+                    let current = self.current();
+                    self.marked[current] = !self.marked[current];
+                    self.move_to(current + 1);
+                }
+                KeyCode::Char('a') => {
+                    // Mark everything, or clear the marks when all are set
+                    let mark = !self.marked.iter().all(|&marked| marked);
+                    self.marked.fill(mark);
+                }
+                KeyCode::Down | KeyCode::Char('j') => self.move_to(self.current() + 1),
+                KeyCode::Up | KeyCode::Char('k') => self.move_to(self.current().saturating_sub(1)),
+                KeyCode::PageDown => self.move_to(self.current() + PAGE_SIZE),
+                KeyCode::PageUp => self.move_to(self.current().saturating_sub(PAGE_SIZE)),
+                KeyCode::Home | KeyCode::Char('g') => self.move_to(0),
+                KeyCode::End | KeyCode::Char('G') => self.move_to(self.results.len()),
+                _ => {}
+            }
+        }
+    }
+
+    fn current(&self) -> usize {
+        self.state.selected().unwrap_or(0)
+    }
+
+    /// Highlight a row, clamped to the list
+    fn move_to(&mut self, index: usize) {
+        self.state
+            .select(Some(index.min(self.results.len().saturating_sub(1))));
+    }
+
+    /// The marked results, or the highlighted one when nothing is marked
+    fn picked(&self) -> Vec<usize> {
+        let marked: Vec<usize> = (0..self.results.len())
+            .filter(|&i| self.marked[i])
+            .collect();
+        if marked.is_empty() && !self.results.is_empty() {
+            vec![self.current()]
+        } else {
+            marked
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [list_area, help_area] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+
+        let items: Vec<ListItem> = self
+            .results
+            .iter()
+            .zip(&self.marked)
+            .map(|(entry, &marked)| ListItem::new(entry_line(entry, marked)))
+            .collect();
+        let marked_count = self.marked.iter().filter(|&&marked| marked).count();
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(format!(
+                " {} results, {} marked ",
+                self.results.len(),
+                marked_count
+            )))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, list_area, &mut self.state);
+
+        let help = Paragraph::new("↑/↓ move  space mark  a mark all  enter continue  esc/q cancel")
+            .style(Style::default().fg(Color::DarkGray));
+        frame.render_widget(help, help_area);
+    }
+}
+
+/// One list row: mark, date, channel, topic and title, duration
+fn entry_line(entry: &mediathekviewweb::models::Item, marked: bool) -> Line<'static> {
+    let date = DateTime::from_timestamp(entry.timestamp, 0)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default();
+    let duration = entry
+        .duration
+        .map_or(String::new(), |d| format!(" ({}min)", d.as_secs() / 60));
+
+    Line::from(vec![
+        Span::styled(
+            if marked { "[x] " } else { "[ ] " },
+            Style::default().fg(Color::Green),
+        ),
+        Span::styled(date, Style::default().fg(Color::Yellow)),
+        Span::raw("  "),
+        Span::styled(entry.channel.clone(), Style::default().fg(Color::Cyan)),
+        Span::raw("  "),
+        Span::raw(format!("{}: {}", entry.topic, entry.title)),
+        Span::styled(duration, Style::default().fg(Color::Green)),
+    ])
+}
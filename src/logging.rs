@@ -14,7 +14,7 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilte
 /// This function sets up a tracing subscriber that:
 /// - Uses structured logging with spans and events
 /// - Filters based on log level (debug when verbose, info otherwise)
-/// - Outputs to stderr with colored formatting
+/// - Outputs to stderr with colored formatting, unless colors are turned off
 /// - Includes module paths and line numbers in verbose mode
 pub fn init_tracing(verbose: bool) {
     if !verbose {
@@ -30,7 +30,7 @@ pub fn init_tracing(verbose: bool) {
         .with_target(true)
         .with_line_number(true)
         .with_file(true)
-        .with_ansi(true)
+        .with_ansi(colored::control::SHOULD_COLORIZE.should_colorize())
         .with_writer(std::io::stderr);
 
     tracing_subscriber::registry()
@@ -1,26 +1,44 @@
 use anyhow::Result;
 use chrono::DateTime;
-use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
 use clap_complete::{generate, Shell as ClapShell};
 use clap_complete_nushell::Nushell;
 use colored::Colorize;
+use futures::StreamExt;
 use mediathekviewweb::{
     models::{SortField, SortOrder},
     Mediathek,
 };
-use regex::Regex;
+use regex::{Regex, RegexBuilder};
 use serde::{Deserialize, Serialize};
 use std::time::Instant;
 
 use std::fs::File;
 use std::io::Write;
+use std::path::{Path, PathBuf};
 
 use std::process::Command;
 
 mod ai;
+mod cache;
+mod config;
+mod download;
+mod http;
+mod interactive;
 mod logging;
-use ai::AIProcessor;
+mod player;
+mod saved_query;
+mod template;
+use ai::{episode_marker, AIOptions, AIProcessor};
+use cache::SearchCache;
+use config::Config;
+use download::{download_videos, DownloadOptions};
+use http::HttpOptions;
+use interactive::pick_results;
 use logging::init_tracing;
+use player::PlayerOptions;
+use saved_query::SavedQuery;
+use template::{parse_template_arg, OutputTemplate};
 
 #[derive(Parser)]
 #[command(name = "mwb")]
@@ -31,6 +49,18 @@ struct Cli {
     #[arg(long, global = true)]
     verbose: bool,
 
+    /// Disable colored output (also set by NO_COLOR, and automatic when stdout isn't a terminal)
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// HTTP(S) proxy for downloads, URL checks and AI requests, e.g. http://proxy.example:3128 (the search API only follows $HTTPS_PROXY)
+    #[arg(long, global = true, value_name = "URL")]
+    proxy: Option<String>,
+
+    /// Give up on a request after this many seconds [default: no limit for MediathekViewWeb, 10s for URL checks, 30s for web pages, 120s for Gemini]
+    #[arg(long, global = true, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+    timeout: Option<u64>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -47,9 +77,77 @@ struct SearchParams {
     exclude_future: bool,
     format: String,
     vlc: Option<String>,
-    vlc_ai: bool,
+    vlc_ai: Option<Vec<String>>,
     xspf_file: bool,
+    xspf_minimal: bool,
+    xspf_compact: bool,
     count: bool,
+    default_channels: Vec<String>,
+    sample: Option<usize>,
+    seed: Option<u64>,
+    with_meta: bool,
+    topics: Vec<String>,
+    exclude_topics: Vec<String>,
+    time: bool,
+    exclude_channels: Vec<String>,
+    channel_case_exact: bool,
+    quality: String,
+    warn_dupes: bool,
+    print_filename: bool,
+    min_description_length: Option<usize>,
+    ascii_fold: bool,
+    verify_urls: bool,
+    max_total_duration: Option<std::time::Duration>,
+    max_title_length: Option<usize>,
+    filter_stats: bool,
+    subtract: Option<String>,
+    percent: bool,
+    edit: bool,
+    group_by_channel: bool,
+    within_group_sort: Option<String>,
+    csv_bom: bool,
+    no_live: bool,
+    after: Option<chrono::NaiveDate>,
+    before: Option<chrono::NaiveDate>,
+    channels: Vec<String>,
+    concurrency: usize,
+    sort_episodes: bool,
+    output: Option<PathBuf>,
+    all: bool,
+    cache_ttl: Option<u64>,
+    template: Option<OutputTemplate>,
+    strict_quality: bool,
+    player: PlayerOptions,
+    play_first: bool,
+    compact: bool,
+    /// `--include-title` and friends, a result must match one pattern of every field listed
+    include_fields: Vec<FieldPattern>,
+    /// `--exclude-title` and friends
+    exclude_fields: Vec<FieldPattern>,
+    case_sensitive: bool,
+    /// `--dedup`, also set by `--dedup-fuzzy`
+    dedup: bool,
+    dedup_fuzzy: bool,
+    fail_if_empty: bool,
+    interactive: bool,
+    /// From the global `--proxy` and `--timeout`
+    http: HttpOptions,
+    /// Set by `mwb download`, which saves the videos instead of printing results
+    download: Option<DownloadOptions>,
+    ai_options: AIOptions,
+}
+
+/// Output formats of `mwb channels`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum ChannelFormat {
+    /// Colored grid with usage tips
+    Grid,
+    /// `[{"channel": ..., "count": N}]`
+    Json,
+    /// `channel,count` records
+    Csv,
+    /// One channel per line
+    Plain,
 }
 
 #[derive(Clone, Debug, ValueEnum)]
@@ -62,67 +160,61 @@ enum Shell {
     Nushell,
 }
 
+// Parsed once per run, so the size of the `Search` and `Download` variants doesn't matter
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand)]
 enum Commands {
     /// Search for content
-    Search {
-        /// Search query (supports `MediathekView` syntax: !channel #topic +title *description >duration <duration)
-        /// Duration examples: ">90" (longer than 90min), "<30" (shorter than 30min), ">60 <120" (between 60-120min)
-        #[arg(required = true)]
-        query: Vec<String>,
-
-        /// Exclude regex patterns (space-separated)
-        #[arg(short, long)]
-        exclude: Option<Vec<String>>,
-
-        /// Include regex patterns - only show results matching these patterns (space-separated)
-        #[arg(short, long)]
-        include: Option<Vec<String>>,
-
-        /// Maximum number of results
-        #[arg(short, long, default_value = "15")]
-        size: u32,
-
-        /// Offset for pagination
-        #[arg(short, long, default_value = "0")]
-        offset: u32,
-
-        /// Sort by field (timestamp, duration, channel)
-        #[arg(short = 'b', long, default_value = "timestamp")]
-        sort_by: String,
-
-        /// Sort order (asc or desc)
-        #[arg(short = 'r', long, default_value = "desc")]
-        sort_order: String,
-
-        /// Exclude future content (default: include future content)
-        #[arg(long = "no-future")]
-        exclude_future: bool,
-
-        /// Output format (table, json, csv, xspf, oneline, onelinetheme, theme-count)
-        #[arg(short = 'f', long, default_value = "onelinetheme")]
-        format: String,
-
-        /// Show only the count of results
-        #[arg(short = 'c', long)]
-        count: bool,
-
-        /// Save video links as XSPF playlist and launch VLC with quality option (l=low, m=medium/default, h=HD)
-        #[arg(short = 'v', long, value_name = "QUALITY", require_equals = true, num_args = 0..=1, default_missing_value = "m")]
-        vlc: Option<String>,
-
-        /// Process results with AI (Gemini) for chronological sorting, deduplication, and VLC playlist creation
-        /// Uses clipboard content for AI web search to find the Wikipedia page
-        #[arg(long = "vlc-ai")]
-        vlc_ai: bool,
-
-        /// Save XSPF playlist to file (use with -f xspf)
-        #[arg(short = 'x', long)]
-        xspf_file: bool,
+    Search(SearchArgs),
+    /// Download the videos a search finds
+    Download {
+        #[command(flatten)]
+        search: SearchArgs,
+
+        /// Directory the videos are saved to (created if missing)
+        #[arg(long, value_name = "DIR", default_value = ".", value_parser = parse_path_arg)]
+        output_dir: PathBuf,
+
+        /// Replace files that already exist instead of skipping them
+        #[arg(long)]
+        overwrite: bool,
+
+        /// Download everything again, ignoring the download manifest and existing files
+        #[arg(long)]
+        force: bool,
+
+        /// How often failed downloads are retried after the first pass
+        #[arg(long, value_name = "N", default_value = "1")]
+        retry_failed_downloads: u32,
     },
     /// List available channels
-    Channels,
+    Channels {
+        /// Only list channels whose name contains this text (case-insensitive), e.g. ard
+        filter: Option<String>,
+
+        /// Number of columns in the channel grid
+        #[arg(long, default_value = "4")]
+        columns: usize,
+
+        /// Fill the grid column by column so alphabetical order reads downwards
+        #[arg(long)]
+        column_major: bool,
+
+        /// Output format
+        #[arg(short = 'f', long, value_enum, default_value = "grid")]
+        format: ChannelFormat,
+
+        /// Show how many of the latest entries each channel has (always included in json and csv)
+        #[arg(long)]
+        counts: bool,
+    },
+    /// Inspect the config file
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
     /// Generate shell completion files
+    #[command(visible_alias = "completions")]
     Completion {
         /// The shell to generate completion for
         #[arg(value_enum)]
@@ -130,8 +222,599 @@ enum Commands {
     },
 }
 
+/// Query, filter and output options shared by `search` and `download`
+#[derive(Args)]
+struct SearchArgs {
+    /// Search query (supports `MediathekView` syntax: !channel #topic +title *description >duration <duration)
+    /// Duration examples: ">90" (longer than 90min), "<30" (shorter than 30min), ">60 <120" or ">60<120" (between 60-120min), ">2h", ">5400s"
+    #[arg(required_unless_present = "from_file")]
+    query: Vec<String>,
+
+    /// Exclude regex patterns (space-separated)
+    #[arg(short, long)]
+    exclude: Option<Vec<String>>,
+
+    /// Include regex patterns - only show results matching these patterns (space-separated)
+    #[arg(short, long)]
+    include: Option<Vec<String>>,
+
+    /// Maximum number of results [default: 15, or `size` from the config file]
+    #[arg(short, long)]
+    size: Option<u32>,
+
+    /// Offset for pagination [default: 0, or `offset` from the config file]
+    #[arg(short, long)]
+    offset: Option<u32>,
+
+    /// Sort by field (timestamp, duration, channel, title, topic) [default: timestamp, or `sort_by` from the config file]
+    #[arg(short = 'b', long)]
+    sort_by: Option<String>,
+
+    /// Sort order (asc or desc) [default: desc, or `sort_order` from the config file]
+    #[arg(short = 'r', long)]
+    sort_order: Option<String>,
+
+    /// Exclude future content (default: include future content, or `exclude_future` from the config file)
+    #[arg(long = "no-future")]
+    exclude_future: bool,
+
+    /// Include future content even if the config file sets `exclude_future`
+    #[arg(long, conflicts_with = "exclude_future")]
+    future: bool,
+
+    /// Output format (table, json, ndjson, yaml, csv, xspf, oneline, onelinetheme, theme-count, rss, jsonl-urls, schedule, vtt-index, brief, plist, id3-csv, template, html, atom)
+    /// [default: onelinetheme, or `format` from the config file]
+    #[arg(short = 'f', long)]
+    format: Option<String>,
+
+    /// Show only the count of results
+    #[arg(short = 'c', long)]
+    count: bool,
+
+    /// Save video links as XSPF playlist and launch VLC with quality option (l=low, m=medium/default, h=HD)
+    #[arg(short = 'v', long, value_name = "QUALITY", require_equals = true, num_args = 0..=1, default_missing_value = "m")]
+    vlc: Option<String>,
+
+    /// Process results with AI (Gemini) for chronological sorting, deduplication, and VLC playlist creation
+    /// Bare --vlc-ai uses no extra search info, --vlc-ai=clipboard passes the clipboard content
+    /// (e.g. a copied episode list), any other value is passed to the AI as is.
+    /// Repeat to give several hints, e.g. one series name or Wikipedia URL per series
+    #[arg(long = "vlc-ai", value_name = "INFO", require_equals = true, num_args = 0..=1, default_missing_value = "", action = clap::ArgAction::Append)]
+    vlc_ai: Option<Vec<String>>,
+
+    /// Save XSPF playlist to file (use with -f xspf)
+    #[arg(short = 'x', long)]
+    xspf_file: bool,
+
+    /// Emit minimal XSPF tracks (only title and location) for picky players
+    #[arg(long)]
+    xspf_minimal: bool,
+
+    /// Emit compact XSPF without whitespace between elements (smaller files for large playlists)
+    #[arg(long)]
+    xspf_compact: bool,
+
+    /// Ignore `default_channels` from the config file and search all channels
+    #[arg(long)]
+    all_channels: bool,
+
+    /// Randomly select N items from the filtered results
+    #[arg(long, value_name = "N")]
+    sample: Option<usize>,
+
+    /// Seed for --sample to get reproducible random subsets
+    #[arg(long, requires = "sample")]
+    seed: Option<u64>,
+
+    /// Add Dublin Core metadata (dc:creator, dc:date, ...) to JSON output for archiving
+    #[arg(long)]
+    with_meta: bool,
+
+    /// Only keep results whose topic equals this value (case-insensitive, repeatable)
+    #[arg(long = "topic", value_name = "TOPIC")]
+    topics: Vec<String>,
+
+    /// Drop results whose topic equals this value (case-insensitive, repeatable)
+    #[arg(long = "exclude-topic", value_name = "TOPIC")]
+    exclude_topics: Vec<String>,
+
+    /// Report per-phase timings (API request, filtering, sorting, output) to stderr
+    #[arg(long)]
+    time: bool,
+
+    /// Drop results from this channel (case-insensitive, repeatable)
+    #[arg(
+        long = "exclude-channel",
+        visible_alias = "not-channel",
+        value_name = "CHANNEL"
+    )]
+    exclude_channels: Vec<String>,
+
+    /// Compare channel names case-sensitively in channel filters
+    #[arg(long)]
+    channel_case_exact: bool,
+
+    /// Video quality for URL-emitting formats like jsonl-urls (l=low, m=medium, h=HD)
+    #[arg(long, default_value = "m")]
+    quality: String,
+
+    /// Warn on stderr when results contain likely duplicate variants of the same title
+    #[arg(long)]
+    warn_dupes: bool,
+
+    /// Print the playlist filename that -x or -v would create, without searching or writing it
+    #[arg(long)]
+    print_filename: bool,
+
+    /// Let the AI search its own queries verbatim instead of appending search hints
+    #[arg(long)]
+    no_enhance_query: bool,
+
+    /// Save the text the AI extracts from each website into this directory (for debugging sorts)
+    #[arg(long, value_name = "DIR", value_parser = parse_path_arg)]
+    ai_dump_extract: Option<PathBuf>,
+
+    /// Drop results whose description is shorter than N characters (or missing)
+    #[arg(long, value_name = "N")]
+    min_description_length: Option<usize>,
+
+    /// Fold umlauts and ß (ä→ae or a, ö→oe or o, ü→ue or u, ß→ss) in include/exclude patterns and result text
+    #[arg(long)]
+    ascii_fold: bool,
+
+    /// Re-sort the AI's playlist locally by season/episode markers if it isn't ascending
+    #[arg(long)]
+    ai_resort: bool,
+
+    /// Check every video URL with a HEAD request and drop unreachable results
+    #[arg(long)]
+    verify_urls: bool,
+
+    /// Stop adding results once their combined duration would exceed this budget (e.g. 2h, 90m, 1h30m)
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration_arg)]
+    max_total_duration: Option<std::time::Duration>,
+
+    /// Re-run the search recorded in a `-f json --with-meta` file (output options still apply)
+    #[arg(long, value_name = "FILE", value_parser = parse_path_arg, conflicts_with = "query")]
+    from_file: Option<PathBuf>,
+
+    /// Truncate titles to this many characters in the oneline formats
+    #[arg(long, value_name = "N")]
+    max_title_length: Option<usize>,
+
+    /// Print how many results each include/exclude pattern kept or removed to stderr
+    #[arg(long)]
+    filter_stats: bool,
+
+    /// Drop results that also appear (same video URL) in the results of this second query
+    #[arg(long, value_name = "QUERY", visible_alias = "then-exclude-results-of")]
+    subtract: Option<String>,
+
+    /// Add a percentage-of-total column to the theme-count report
+    #[arg(long)]
+    percent: bool,
+
+    /// Open the written playlist (-x or -v) in $EDITOR before playing it
+    #[arg(long)]
+    edit: bool,
+
+    /// Group results by channel (table output gets a heading per channel)
+    #[arg(long)]
+    group_by_channel: bool,
+
+    /// Sort field within each channel group (timestamp, duration, title, topic), ordered by --sort-order
+    #[arg(long, value_name = "FIELD", requires = "group_by_channel", value_parser = ["timestamp", "duration", "title", "topic"])]
+    within_group_sort: Option<String>,
+
+    /// Start CSV output (-f csv, id3-csv) with a UTF-8 byte order mark for Excel
+    #[arg(long)]
+    csv_bom: bool,
+
+    /// Drop livestream entries, which can't be played from a playlist
+    #[arg(long, visible_alias = "exclude-live")]
+    no_live: bool,
+
+    /// Only keep results aired on or after this date (YYYY-MM-DD, UTC)
+    #[arg(long, value_name = "DATE", value_parser = parse_date_arg)]
+    after: Option<chrono::NaiveDate>,
+
+    /// Only keep results aired before this date (YYYY-MM-DD, UTC, exclusive)
+    #[arg(long, value_name = "DATE", value_parser = parse_date_arg)]
+    before: Option<chrono::NaiveDate>,
+
+    /// Only keep results from this channel (case-insensitive, repeatable)
+    #[arg(long = "channel", value_name = "CHANNEL")]
+    channels: Vec<String>,
+
+    /// Number of multi-search API requests in flight at once
+    #[arg(long, default_value = "4", value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    concurrency: usize,
+
+    /// Sort episodes by their (S2/E10) or trailing (234) markers and launch VLC, without the AI.
+    /// Falls back to --vlc-ai processing when no title carries a marker
+    #[arg(long)]
+    sort_episodes: bool,
+
+    /// Most episodes sent to the AI (0 = no limit, mind your API quota)
+    #[arg(long, value_name = "N", default_value_t = ai::DEFAULT_MAX_EPISODES)]
+    ai_max_episodes: usize,
+
+    /// Gemini model for --vlc-ai, e.g. gemini-2.5-pro [default: $MWB_GEMINI_MODEL or gemini-2.5-flash-lite]
+    #[arg(long, value_name = "MODEL")]
+    ai_model: Option<String>,
+
+    /// Episode list page (e.g. a Wikipedia article) the AI reads up front instead of searching for one
+    #[arg(long, value_name = "URL", value_parser = parse_url_arg)]
+    wiki_url: Option<String>,
+
+    /// Write the formatted results (-f) to FILE instead of stdout, replacing an existing file
+    #[arg(long, value_name = "FILE", value_parser = parse_path_arg)]
+    output: Option<PathBuf>,
+
+    /// Page through every result (in --size pages from --offset) instead of fetching one page
+    #[arg(long)]
+    all: bool,
+
+    /// Cache API responses on disk for SECONDS and reuse them for identical searches
+    /// [default: off, or `cache_ttl` from the config file]
+    #[arg(long, value_name = "SECONDS")]
+    cache_ttl: Option<u64>,
+
+    /// Don't read or write the response cache, even if `cache_ttl` is configured
+    #[arg(long, conflicts_with = "cache_ttl")]
+    no_cache: bool,
+
+    /// Line layout for `-f template`, e.g. "{date} {channel}: {title} {url_hd}" (implies `-f template`)
+    /// [placeholders: channel, topic, title, date, time, duration, url, url_low, url_hd, website, subtitle, desc, id]
+    #[arg(long, value_name = "FORMAT", value_parser = parse_template_arg)]
+    template: Option<OutputTemplate>,
+
+    /// Leave results without the requested -v/--quality version out of VLC playlists instead of using medium quality
+    #[arg(long)]
+    strict_quality: bool,
+
+    /// Media player that opens generated playlists, e.g. mpv (default: $MWB_PLAYER, else VLC)
+    #[arg(long, value_name = "PATH")]
+    player: Option<String>,
+
+    /// Write playlists (-v, --vlc-ai, --sort-episodes) without starting a player
+    #[arg(long)]
+    no_launch: bool,
+
+    /// Extra arguments passed verbatim to the player after the playlist, e.g. "--fullscreen" (quote words with spaces)
+    #[arg(long, value_name = "ARGS", allow_hyphen_values = true)]
+    player_args: Option<String>,
+
+    /// Play the first result (after filtering and sorting) right away in the player, without a playlist
+    #[arg(long, conflicts_with = "no_launch")]
+    play_first: bool,
+
+    /// Print `-f json` as a single compact line instead of pretty-printed
+    #[arg(long)]
+    compact: bool,
+
+    /// Only keep results whose title matches this regex (case-insensitive, repeatable)
+    #[arg(long, value_name = "REGEX")]
+    include_title: Vec<String>,
+
+    /// Only keep results whose topic matches this regex (case-insensitive, repeatable; --topic matches exactly)
+    #[arg(long, value_name = "REGEX")]
+    include_topic_regex: Vec<String>,
+
+    /// Only keep results whose channel matches this regex (case-insensitive, repeatable; --channel matches exactly)
+    #[arg(long, value_name = "REGEX")]
+    include_channel_regex: Vec<String>,
+
+    /// Only keep results whose description matches this regex (case-insensitive, repeatable)
+    #[arg(long, value_name = "REGEX")]
+    include_description: Vec<String>,
+
+    /// Drop results whose title matches this regex (case-insensitive, repeatable)
+    #[arg(long, value_name = "REGEX")]
+    exclude_title: Vec<String>,
+
+    /// Drop results whose topic matches this regex (case-insensitive, repeatable; --exclude-topic matches exactly)
+    #[arg(long, value_name = "REGEX")]
+    exclude_topic_regex: Vec<String>,
+
+    /// Drop results whose channel matches this regex (case-insensitive, repeatable; --exclude-channel matches exactly)
+    #[arg(long, value_name = "REGEX")]
+    exclude_channel_regex: Vec<String>,
+
+    /// Drop results whose description matches this regex (case-insensitive, repeatable)
+    #[arg(long, value_name = "REGEX")]
+    exclude_description: Vec<String>,
+
+    /// Match the -i/-e and field-scoped regex filters case-sensitively
+    #[arg(long)]
+    case_sensitive: bool,
+
+    /// Drop results whose video URL repeats an earlier one, keeping the first
+    #[arg(long)]
+    dedup: bool,
+
+    /// Like --dedup, and also collapse titles that only differ by a variant
+    /// suffix such as "(Audiodeskription)" or "(HD)"
+    #[arg(long)]
+    dedup_fuzzy: bool,
+
+    /// Exit with status 3 when no result is left after filtering
+    #[arg(long)]
+    fail_if_empty: bool,
+
+    /// Pick results in a scrollable list (space marks, enter confirms, esc cancels),
+    /// the picks are opened in the player unless another action like --download is given
+    #[arg(long, conflicts_with = "count")]
+    interactive: bool,
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Print where the config file is read from (it may not exist yet)
+    Path,
+}
+
 const USER_AGENT: &str = "mwb-cli/1.0";
 
+/// Turn off colored output for `--no-color`, a non-empty `NO_COLOR`
+/// (<https://no-color.org>), or when stdout isn't a terminal
+///
+/// Piped output like `-f oneline | grep` would otherwise carry ANSI escape codes.
+/// `CLICOLOR_FORCE` keeps colors on for pipes that render them, e.g. `less -R`.
+fn init_color(no_color: bool) {
+    use std::io::IsTerminal;
+
+    let env_set = |name: &str| std::env::var_os(name).is_some_and(|value| !value.is_empty());
+
+    let piped = !std::io::stdout().is_terminal() && !env_set("CLICOLOR_FORCE");
+
+    if no_color || env_set("NO_COLOR") || piped {
+        colored::control::set_override(false);
+    }
+}
+
+/// Writer for the formatted results: `--output` file, or stdout
+///
+/// Colors are turned off for a file so it doesn't end up with ANSI escape codes.
+fn open_output(path: Option<&Path>) -> Result<Box<dyn Write>> {
+    let Some(path) = path else {
+        return Ok(Box::new(std::io::stdout().lock()));
+    };
+
+    colored::control::set_override(false);
+    let file = File::create(path)
+        .map_err(|e| anyhow::anyhow!("Failed to create output file {}: {}", path.display(), e))?;
+    tracing::info!(path = %path.display(), "Writing results to file");
+
+    Ok(Box::new(std::io::BufWriter::new(file)))
+}
+
+/// Flush the results writer and confirm a written `--output` file on stderr
+fn finish_output(mut out: Box<dyn Write>, path: Option<&Path>, result_count: usize) -> Result<()> {
+    out.flush()?;
+    if let Some(path) = path {
+        eprintln!("Wrote {} result(s) to {}", result_count, path.display());
+    }
+    Ok(())
+}
+
+/// Per-phase durations collected for `--time`
+struct PhaseTimings {
+    enabled: bool,
+    phases: Vec<(String, std::time::Duration)>,
+}
+
+impl PhaseTimings {
+    fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            phases: Vec::new(),
+        }
+    }
+
+    fn record(&mut self, phase: impl Into<String>, duration: std::time::Duration) {
+        if self.enabled {
+            self.phases.push((phase.into(), duration));
+        }
+    }
+
+    /// Print the collected timings to stderr so they don't mix with the results
+    fn report(&self) {
+        if !self.enabled {
+            return;
+        }
+
+        let width = self
+            .phases
+            .iter()
+            .map(|(phase, _)| phase.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(5);
+        let total: std::time::Duration = self.phases.iter().map(|(_, d)| *d).sum();
+
+        eprintln!("{}", "⏱️  Timings".bold());
+        for (phase, duration) in &self.phases {
+            eprintln!(
+                "  {:<width$} {:>8.1}ms",
+                phase,
+                duration.as_secs_f64() * 1000.0,
+                width = width
+            );
+        }
+        eprintln!(
+            "  {:<width$} {:>8.1}ms",
+            "total",
+            total.as_secs_f64() * 1000.0,
+            width = width
+        );
+    }
+}
+
+/// Expand a leading `~` to the home directory and `$VAR`, `${VAR}` and
+/// `%VAR%` references to environment variable values
+///
+/// Unknown variables are left as written, so a typo shows up in the resulting
+/// path instead of silently disappearing.
+fn expand_path(path: &str) -> PathBuf {
+    let mut expanded = String::with_capacity(path.len());
+    let mut rest = path;
+
+    if rest == "~" || rest.starts_with("~/") || rest.starts_with("~\\") {
+        if let Some(home) = dirs::home_dir() {
+            expanded.push_str(&home.to_string_lossy());
+            rest = &rest[1..];
+        }
+    }
+
+    while let Some(pos) = rest.find(['$', '%']) {
+        expanded.push_str(&rest[..pos]);
+        let marker = &rest[pos..];
+
+        let (name, consumed) = if let Some(braced) = marker.strip_prefix("${") {
+            match braced.find('}') {
+                Some(end) => (&braced[..end], end + 3),
+                None => ("", 0),
+            }
+        } else if let Some(unix) = marker.strip_prefix('$') {
+            let end = unix
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(unix.len());
+            (&unix[..end], end + 1)
+        } else {
+            let windows = &marker[1..];
+            match windows.find('%') {
+                Some(end) => (&windows[..end], end + 2),
+                None => ("", 0),
+            }
+        };
+
+        match std::env::var(name) {
+            Ok(value) if !name.is_empty() => {
+                expanded.push_str(&value);
+                rest = &marker[consumed..];
+            }
+            _ => {
+                // Not a (known) variable reference, keep the marker character
+                expanded.push_str(&marker[..1]);
+                rest = &marker[1..];
+            }
+        }
+    }
+    expanded.push_str(rest);
+
+    PathBuf::from(expanded)
+}
+
+/// Clap value parser for path flags, applying `expand_path`
+fn parse_path_arg(value: &str) -> Result<PathBuf, String> {
+    Ok(expand_path(value))
+}
+
+/// Parse a human-friendly duration like `2h`, `90m`, `1h30m` or `45s`
+///
+/// A bare number is taken as minutes, matching the `>min`/`<min` selectors.
+fn parse_duration_arg(value: &str) -> Result<std::time::Duration, String> {
+    let value = value.trim().to_lowercase();
+    let invalid = || format!("invalid duration '{value}', use e.g. 2h, 90m, 1h30m or 45s");
+
+    if let Ok(minutes) = value.parse::<u64>() {
+        let secs = minutes.checked_mul(60).ok_or_else(invalid)?;
+        return Ok(std::time::Duration::from_secs(secs));
+    }
+
+    let mut total_secs = 0u64;
+    let mut number = String::new();
+    for c in value.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let amount: u64 = number.parse().map_err(|_| invalid())?;
+        let unit_secs = match c {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return Err(invalid()),
+        };
+        total_secs = amount
+            .checked_mul(unit_secs)
+            .and_then(|secs| total_secs.checked_add(secs))
+            .ok_or_else(invalid)?;
+        number.clear();
+    }
+    if !number.is_empty() || total_secs == 0 {
+        return Err(invalid());
+    }
+
+    Ok(std::time::Duration::from_secs(total_secs))
+}
+
+/// Parse an http(s) URL for `--wiki-url`
+fn parse_url_arg(value: &str) -> Result<String, String> {
+    let url = url::Url::parse(value.trim()).map_err(|e| format!("invalid URL '{value}' ({e})"))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!("'{value}' is not an http(s) URL"));
+    }
+    Ok(url.to_string())
+}
+
+/// Parse a `YYYY-MM-DD` date for `--after`/`--before`
+fn parse_date_arg(value: &str) -> Result<chrono::NaiveDate, String> {
+    chrono::NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+        .map_err(|e| format!("invalid date '{value}' ({e}), use YYYY-MM-DD, e.g. 2024-03-31"))
+}
+
+/// Resolve the `--vlc-ai` values into the hints handed to the AI, in order
+///
+/// Empty hints (bare `--vlc-ai`, an empty clipboard) are dropped.
+fn get_search_hints(vlc_ai: &[String]) -> Result<Vec<String>> {
+    let mut hints = Vec::new();
+    for value in vlc_ai {
+        if let Some(hint) = get_search_info(value)? {
+            hints.push(hint);
+        }
+    }
+    Ok(hints)
+}
+
+/// Resolve a single `--vlc-ai` value into extra search info for the AI
+///
+/// * `""` (bare `--vlc-ai`) - no extra search info
+/// * `clipboard` - the clipboard content, e.g. a copied episode list
+/// * anything else - used verbatim
+fn get_search_info(vlc_ai: &str) -> Result<Option<String>> {
+    match vlc_ai.trim() {
+        "" => {
+            tracing::info!("No search info given for AI processing");
+            Ok(None)
+        }
+        "clipboard" => get_clipboard_content(),
+        info => {
+            tracing::info!(search_info_length = %info.len(), "Using search info from command line");
+            Ok(Some(info.to_string()))
+        }
+    }
+}
+
+/// Characters of the clipboard content shown before processing
+const CLIPBOARD_PREVIEW_CHARS: usize = 50;
+
+/// Shorten clipboard content for display, cutting at a character boundary so
+/// umlauts and other multibyte characters never split
+fn clipboard_preview(text: &str) -> String {
+    if text.chars().count() > CLIPBOARD_PREVIEW_CHARS {
+        format!(
+            "{}...",
+            text.chars()
+                .take(CLIPBOARD_PREVIEW_CHARS)
+                .collect::<String>()
+        )
+    } else {
+        text.to_string()
+    }
+}
+
 fn get_clipboard_content() -> Result<Option<String>> {
     tracing::info!("Attempting to read clipboard content");
 
@@ -150,15 +833,8 @@ fn get_clipboard_content() -> Result<Option<String>> {
                     tracing::info!(clipboard_length = %trimmed.len(), "Successfully read clipboard content");
                     println!(
                         "{}",
-                        format!(
-                            "📋 Using clipboard content: {}",
-                            if trimmed.len() > 50 {
-                                format!("{}...", &trimmed[..50])
-                            } else {
-                                trimmed.to_string()
-                            }
-                        )
-                        .cyan()
+                        format!("📋 Using clipboard content: {}", clipboard_preview(trimmed))
+                            .cyan()
                     );
                     Ok(Some(trimmed.to_string()))
                 }
@@ -183,47 +859,62 @@ fn get_clipboard_content() -> Result<Option<String>> {
 async fn main() -> Result<()> {
     let cli = Cli::parse();
 
+    // Decide on colors first, the tracing output follows the same choice
+    init_color(cli.no_color);
+
     // Initialize tracing based on global verbose flag
     init_tracing(cli.verbose);
 
+    let http = HttpOptions::new(cli.proxy.as_deref(), cli.timeout)?;
+
     let client = Mediathek::new(USER_AGENT.parse()?)?;
 
     match cli.command {
-        Commands::Search {
-            query,
-            exclude,
-            include,
-            size,
-            offset,
-            sort_by,
-            sort_order,
-            exclude_future,
-            format,
-            vlc,
-            vlc_ai,
-            xspf_file,
-            count,
+        Commands::Search(args) => {
+            let params = search_params(args, http)?;
+            search_content(&client, params).await?;
+        }
+        Commands::Download {
+            search,
+            output_dir,
+            overwrite,
+            force,
+            retry_failed_downloads,
         } => {
-            let params = SearchParams {
-                query_terms: query,
-                exclude_patterns: exclude,
-                include_patterns: include,
-                size,
-                offset,
-                sort_by,
-                sort_order,
-                exclude_future,
-                format,
-                vlc,
-                vlc_ai,
-                xspf_file,
-                count,
-            };
+            let mut params = search_params(search, http)?;
+            params.download = Some(DownloadOptions {
+                output_dir,
+                overwrite,
+                force,
+                http: params.http.clone(),
+                retries: retry_failed_downloads,
+            });
             search_content(&client, params).await?;
         }
-        Commands::Channels => {
-            list_channels(&client).await?;
+        Commands::Channels {
+            filter,
+            columns,
+            column_major,
+            format,
+            counts,
+        } => {
+            list_channels(
+                &client,
+                filter.as_deref(),
+                columns,
+                column_major,
+                format,
+                counts,
+                http.timeout,
+            )
+            .await?;
         }
+        Commands::Config { action } => match action {
+            ConfigAction::Path => match config::config_path() {
+                Some(path) => println!("{}", path.display()),
+                None => anyhow::bail!("Could not determine the config directory"),
+            },
+        },
         Commands::Completion { shell } => {
             let mut cmd = Cli::command();
             let bin_name = cmd.get_name().to_string();
@@ -263,30 +954,246 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Turn the search arguments into search parameters, filling in config file
+/// defaults and a `--from-file` saved query
+fn search_params(args: SearchArgs, http: HttpOptions) -> Result<SearchParams> {
+    let SearchArgs {
+        query,
+        exclude,
+        include,
+        size,
+        offset,
+        sort_by,
+        sort_order,
+        exclude_future,
+        future,
+        format,
+        vlc,
+        vlc_ai,
+        xspf_file,
+        xspf_minimal,
+        xspf_compact,
+        all_channels,
+        sample,
+        seed,
+        with_meta,
+        topics,
+        exclude_topics,
+        time,
+        exclude_channels,
+        channel_case_exact,
+        quality,
+        warn_dupes,
+        print_filename,
+        no_enhance_query,
+        ai_dump_extract,
+        min_description_length,
+        ascii_fold,
+        ai_resort,
+        verify_urls,
+        max_total_duration,
+        from_file,
+        max_title_length,
+        filter_stats,
+        subtract,
+        percent,
+        edit,
+        group_by_channel,
+        within_group_sort,
+        csv_bom,
+        no_live,
+        after,
+        before,
+        channels,
+        concurrency,
+        sort_episodes,
+        ai_max_episodes,
+        ai_model,
+        wiki_url,
+        output,
+        all,
+        cache_ttl,
+        no_cache,
+        template,
+        strict_quality,
+        player,
+        no_launch,
+        player_args,
+        play_first,
+        compact,
+        include_title,
+        include_topic_regex,
+        include_channel_regex,
+        include_description,
+        exclude_title,
+        exclude_topic_regex,
+        exclude_channel_regex,
+        exclude_description,
+        case_sensitive,
+        dedup,
+        dedup_fuzzy,
+        fail_if_empty,
+        interactive,
+        count,
+    } = args;
+
+    // Command line flags win over the config file, which wins over the built-in defaults
+    let config = Config::load()?;
+    let player = PlayerOptions::new(player, player_args.as_deref(), no_launch)?;
+    let mut params = SearchParams {
+        query_terms: query,
+        exclude_patterns: exclude,
+        include_patterns: include,
+        size: size.or(config.size).unwrap_or(15),
+        offset: offset.or(config.offset).unwrap_or(0),
+        sort_by: sort_by
+            .or(config.sort_by)
+            .unwrap_or_else(|| "timestamp".to_string()),
+        sort_order: sort_order
+            .or(config.sort_order)
+            .unwrap_or_else(|| "desc".to_string()),
+        exclude_future: exclude_future || (!future && config.exclude_future.unwrap_or(false)),
+        format: format
+            .or_else(|| template.is_some().then(|| "template".to_string()))
+            .or(config.format)
+            .unwrap_or_else(|| "onelinetheme".to_string()),
+        vlc,
+        vlc_ai,
+        xspf_file,
+        xspf_minimal,
+        xspf_compact,
+        count,
+        default_channels: if all_channels {
+            Vec::new()
+        } else {
+            config.default_channels
+        },
+        sample,
+        seed,
+        with_meta,
+        topics,
+        exclude_topics,
+        time,
+        exclude_channels,
+        channel_case_exact,
+        quality,
+        warn_dupes,
+        print_filename,
+        min_description_length,
+        ascii_fold,
+        verify_urls,
+        max_total_duration,
+        max_title_length,
+        filter_stats,
+        subtract,
+        percent,
+        edit,
+        group_by_channel,
+        within_group_sort,
+        csv_bom,
+        no_live,
+        after,
+        before,
+        channels,
+        concurrency,
+        sort_episodes,
+        output,
+        all,
+        cache_ttl: if no_cache {
+            None
+        } else {
+            cache_ttl.or(config.cache_ttl)
+        },
+        template,
+        strict_quality,
+        player: player.clone(),
+        play_first,
+        compact,
+        include_fields: FieldPattern::collect(&[
+            (FilterField::Title, include_title),
+            (FilterField::Topic, include_topic_regex),
+            (FilterField::Channel, include_channel_regex),
+            (FilterField::Description, include_description),
+        ]),
+        exclude_fields: FieldPattern::collect(&[
+            (FilterField::Title, exclude_title),
+            (FilterField::Topic, exclude_topic_regex),
+            (FilterField::Channel, exclude_channel_regex),
+            (FilterField::Description, exclude_description),
+        ]),
+        case_sensitive,
+        dedup: dedup || dedup_fuzzy,
+        dedup_fuzzy,
+        fail_if_empty,
+        interactive,
+        http: http.clone(),
+        download: None,
+        ai_options: AIOptions {
+            episode_fields: config
+                .ai_fields
+                .unwrap_or_else(|| AIOptions::default().episode_fields),
+            enhance_query: !no_enhance_query,
+            dump_extract_dir: ai_dump_extract,
+            resort_episodes: ai_resort,
+            max_episodes: ai_max_episodes,
+            model: ai_model,
+            wiki_url,
+            player,
+            http,
+        },
+    };
+    if let Some(path) = from_file {
+        SavedQuery::load(&path)?.apply_to(&mut params);
+    }
+    if params.format == "template" && params.template.is_none() {
+        anyhow::bail!(
+            "-f template needs a layout, e.g. --template \"{{date}} {{channel}}: {{title}}\""
+        );
+    }
+    if let (Some(after), Some(before)) = (params.after, params.before) {
+        if after >= before {
+            anyhow::bail!(
+                "--after {} must be earlier than --before {}, no result could match",
+                after,
+                before
+            );
+        }
+    }
+
+    Ok(params)
+}
+
 async fn search_content(client: &Mediathek, params: SearchParams) -> Result<()> {
+    let params = SearchParams {
+        query_terms: normalize_query_terms(params.query_terms),
+        ..params
+    };
+
+    // Filenames only depend on the query, so no request is needed
+    if params.print_filename {
+        // --interactive picks without another action become a -v playlist
+        let vlc = params.vlc.is_some() || params.interactive;
+        let filename = playlist_filename(Path::new("."), &params.query_terms.join(" "), vlc);
+        println!("{filename}");
+        return Ok(());
+    }
+
     // Multi-search mode: perform separate searches for each query term
     if params.query_terms.len() > 1 {
         return multi_search_content(client, params).await;
     }
 
-    let query_string = params.query_terms.join(" ");
+    let mut timings = PhaseTimings::new(params.time);
 
-    // Preprocess query to extract duration selectors and search terms
-    let (search_terms_only, duration_filters) = extract_duration_selectors(&query_string);
+    let query_string =
+        apply_default_channels(&params.query_terms.join(" "), &params.default_channels);
 
-    // Build the query using the mediathekviewweb crate
-    // Use search terms without duration selectors for natural all-field search
-    let mut query_builder = if search_terms_only.is_empty() {
-        // Duration-only query
-        client.query_string("", false)
-    } else {
-        // Let the API handle natural search across all fields
-        client.query_string(&search_terms_only, false)
-    };
+    // Preprocess query to extract duration selectors and search terms
+    let (search_terms_only, duration_range) = extract_duration_selectors(&query_string);
 
     tracing::info!(
         original_query = %query_string,
-        duration_filters = ?duration_filters,
+        duration_range = ?duration_range,
         search_terms = %search_terms_only,
         size = %params.size,
         offset = %params.offset,
@@ -295,67 +1202,147 @@ async fn search_content(client: &Mediathek, params: SearchParams) -> Result<()>
         exclude_future = %params.exclude_future,
         exclude_patterns = ?params.exclude_patterns,
         include_patterns = ?params.include_patterns,
+        all = %params.all,
         "Starting MediathekView search request"
     );
 
-    // Apply duration filters extracted from the query
-    for filter in duration_filters {
-        if let Some(duration_str) = filter.strip_prefix('>') {
-            if let Ok(min_duration) = duration_str.parse::<u64>() {
-                query_builder =
-                    query_builder.duration_min(std::time::Duration::from_secs(min_duration * 60));
-            }
-        } else if let Some(duration_str) = filter.strip_prefix('<') {
-            if let Ok(max_duration) = duration_str.parse::<u64>() {
-                query_builder =
-                    query_builder.duration_max(std::time::Duration::from_secs(max_duration * 60));
-            }
+    // Build the query for one page using the mediathekviewweb crate, --all requests several
+    let build_query = |offset: u32| {
+        // Use search terms without duration selectors for natural all-field search
+        let mut query_builder = if search_terms_only.is_empty() {
+            // Duration-only query
+            client.query_string("", false)
+        } else {
+            // Let the API handle natural search across all fields
+            client.query_string(&search_terms_only, false)
+        };
+
+        // Apply duration filters extracted from the query
+        if let Some(min_duration) = duration_range.min {
+            query_builder = query_builder.duration_min(min_duration);
+        }
+        if let Some(max_duration) = duration_range.max {
+            query_builder = query_builder.duration_max(max_duration);
         }
-    }
 
-    // Apply other parameters
-    query_builder = query_builder
-        .include_future(!params.exclude_future)
-        .size(params.size as usize)
-        .offset(params.offset as usize);
+        // Apply other parameters
+        query_builder = query_builder
+            .include_future(!params.exclude_future)
+            .size(params.size as usize)
+            .offset(offset as usize);
 
-    // Apply sorting
-    let sort_field = match params.sort_by.as_str() {
-        "duration" => SortField::Duration,
-        "channel" => SortField::Channel,
-        _ => SortField::Timestamp, // includes "timestamp" and default
-    };
+        // Apply sorting
+        // title and topic aren't API sort fields, they are sorted after the fetch
+        let sort_field = match params.sort_by.as_str() {
+            "duration" => SortField::Duration,
+            "channel" => SortField::Channel,
+            _ => SortField::Timestamp, // includes "timestamp", "title", "topic" and default
+        };
 
-    let sort_direction = match params.sort_order.as_str() {
-        "asc" => SortOrder::Ascending,
-        _ => SortOrder::Descending,
-    };
+        let sort_direction = match params.sort_order.as_str() {
+            "asc" => SortOrder::Ascending,
+            _ => SortOrder::Descending,
+        };
 
-    query_builder = query_builder.sort_by(sort_field).sort_order(sort_direction);
+        query_builder.sort_by(sort_field).sort_order(sort_direction)
+    };
 
-    // Execute the query
+    // Execute the query, or reuse a cached response
     let start_time = Instant::now();
 
-    tracing::info!("Executing MediathekView API request");
+    let cache = SearchCache::open(params.cache_ttl);
+    let cache_key = search_cache_key(&query_string, &params);
+    let cached = cache.as_ref().and_then(|cache| cache.load(&cache_key));
+
+    let (results, query_info) = match cached {
+        Some(cached) => {
+            report_cache_hit(cached.age_secs());
+            cached.into_parts()
+        }
+        None => {
+            tracing::info!("Executing MediathekView API request");
+
+            let mut result =
+                send_api_request(build_query(params.offset).send(), params.http.timeout).await?;
+
+            if params.all {
+                fetch_remaining_pages(
+                    &mut result.results,
+                    result.query_info.total_results,
+                    params.offset,
+                    |offset| {
+                        let query = build_query(offset);
+                        let timeout = params.http.timeout;
+                        async move { Ok(send_api_request(query.send(), timeout).await?.results) }
+                    },
+                )
+                .await?;
+            }
 
-    let result = query_builder.send().await?;
+            if let Some(cache) = &cache {
+                cache.store(&cache_key, &result.results, &result.query_info);
+            }
+            (result.results, result.query_info)
+        }
+    };
 
     let duration = start_time.elapsed();
+    timings.record("API request", duration);
     tracing::info!(
         response_time_ms = %duration.as_millis(),
-        results_found = %result.results.len(),
-        total_available = %result.query_info.total_results,
+        results_found = %results.len(),
+        total_available = %query_info.total_results,
         "MediathekView API request completed"
     );
 
+    warn_on_result_anomalies(results.len(), &query_info, params.offset);
+    warn_on_stale_filmliste(&query_info);
+
+    let stats = SearchStats::single(&query_info);
+    process_results(client, params, results, Some(query_info), stats, timings).await
+}
+
+/// Sort, filter and output the fetched results, shared by single and multi search
+///
+/// `query_info` is the API response's, `None` for merged multi-search results.
+async fn process_results(
+    client: &Mediathek,
+    params: SearchParams,
+    mut results: Vec<mediathekviewweb::models::Item>,
+    query_info: Option<mediathekviewweb::models::QueryInfo>,
+    stats: SearchStats,
+    mut timings: PhaseTimings,
+) -> Result<()> {
+    // Recorded up front, the filter patterns are consumed while filtering
+    let meta = params.with_meta.then(|| SavedQuery::from_params(&params));
+
+    // Also for fields the API sorted by: its order of equal items varies between
+    // requests, the local sort breaks those ties the same way every time
+    let sort_start = Instant::now();
+    results.sort_by(|a, b| compare_items(a, b, &params.sort_by, &params.sort_order));
+    timings.record("sorting", sort_start.elapsed());
+
+    let filter_start = Instant::now();
+
     // Save original count before moving results
-    let original_count = result.results.len();
+    let original_count = results.len();
 
     // Apply client-side regex filters
     let filtered_results = apply_regex_filters(
-        result.results,
+        results,
         params.exclude_patterns,
         params.include_patterns,
+        params.ascii_fold,
+        params.case_sensitive,
+        params.filter_stats,
+    )?;
+    let filtered_results = apply_field_regex_filters(
+        filtered_results,
+        &params.include_fields,
+        &params.exclude_fields,
+        params.ascii_fold,
+        params.case_sensitive,
+        params.filter_stats,
     )?;
 
     if filtered_results.len() != original_count {
@@ -366,56 +1353,383 @@ async fn search_content(client: &Mediathek, params: SearchParams) -> Result<()>
         );
     }
 
+    let filtered_results =
+        apply_topic_filters(filtered_results, &params.topics, &params.exclude_topics);
+    let filtered_results = apply_channel_filters(
+        filtered_results,
+        &params.channels,
+        &params.exclude_channels,
+        params.channel_case_exact,
+    );
+    let filtered_results =
+        apply_description_length_filter(filtered_results, params.min_description_length);
+    let filtered_results = if params.no_live {
+        drop_livestreams(filtered_results)
+    } else {
+        filtered_results
+    };
+    let filtered_results = apply_date_range_filter(filtered_results, params.after, params.before);
+    let filtered_results = if params.dedup {
+        dedup_results(filtered_results, params.dedup_fuzzy)
+    } else {
+        filtered_results
+    };
+
+    let filtered_results = match &params.subtract {
+        Some(subtract_query) => {
+            subtract_query_results(
+                client,
+                filtered_results,
+                subtract_query,
+                params.exclude_future,
+                params.http.timeout,
+            )
+            .await?
+        }
+        None => filtered_results,
+    };
+
+    let filtered_results = match params.sample {
+        Some(sample_size) => sample_results(filtered_results, sample_size, params.seed),
+        None => filtered_results,
+    };
+
+    let filtered_results = if params.verify_urls {
+        verify_urls(filtered_results, &params.http).await
+    } else {
+        filtered_results
+    };
+
+    let filtered_results = match params.max_total_duration {
+        Some(budget) => limit_total_duration(filtered_results, budget),
+        None => filtered_results,
+    };
+
+    let filtered_results = if params.group_by_channel {
+        group_by_channel(
+            filtered_results,
+            params.within_group_sort.as_deref(),
+            &params.sort_order,
+        )
+    } else {
+        filtered_results
+    };
+
+    if params.warn_dupes {
+        warn_about_duplicates(&filtered_results);
+    }
+
+    timings.record("filtering", filter_start.elapsed());
+
+    let filtered_results = if params.interactive && !filtered_results.is_empty() {
+        match pick_results(&filtered_results)? {
+            Some(picked) => picked,
+            None => {
+                println!("{}", "Selection cancelled".yellow());
+                return Ok(());
+            }
+        }
+    } else {
+        filtered_results
+    };
+
+    let output_start = Instant::now();
+
     if params.count {
         println!("{}", filtered_results.len());
-    } else if params.vlc_ai {
-        let search_info = get_clipboard_content()?;
-        process_with_ai(&filtered_results, search_info.as_deref()).await?;
-    } else if let Some(quality) = params.vlc {
-        // Validate quality parameter and set default if invalid
-        let validated_quality = match quality.as_str() {
-            "l" | "low" => "l",
-            "h" | "hd" | "high" => "h",
-            "m" | "medium" | "" => "m",
-            _ => {
-                println!("{}", format!("Warning: Invalid quality '{quality}'. Using medium quality (m). Valid options: l (low), m (medium), h (HD)").yellow());
-                "m"
-            }
-        };
-        create_vlc_playlist_and_launch(&filtered_results, &params.query_terms, validated_quality)?;
+    } else if let Some(download) = &params.download {
+        download_videos(
+            &filtered_results,
+            validate_quality(&params.quality),
+            download,
+        )
+        .await?;
+    } else if params.play_first {
+        // Without -v the video uses --quality
+        let quality = validate_quality(params.vlc.as_deref().unwrap_or(&params.quality));
+        play_first(&filtered_results, quality, &params.player)?;
+    } else if let Some(sorted) = params
+        .sort_episodes
+        .then(|| sort_by_episode(&filtered_results))
+        .flatten()
+    {
+        // Without -v the playlist uses --quality
+        let quality = validate_quality(params.vlc.as_deref().unwrap_or(&params.quality));
+        create_vlc_playlist_and_launch(
+            &sorted,
+            &params.query_terms,
+            quality,
+            params.xspf_minimal,
+            params.xspf_compact,
+            params.edit,
+            params.strict_quality,
+            &params.player,
+        )?;
+    } else if params.sort_episodes || params.vlc_ai.is_some() {
+        let search_info = get_search_hints(params.vlc_ai.as_deref().unwrap_or_default())?;
+        process_with_ai(&filtered_results, &search_info, params.ai_options.clone()).await?;
+    } else if let Some(quality) = params
+        .vlc
+        // --interactive picks are played unless another action was asked for
+        .or_else(|| params.interactive.then(|| params.quality.clone()))
+    {
+        let validated_quality = validate_quality(&quality);
+        create_vlc_playlist_and_launch(
+            &filtered_results,
+            &params.query_terms,
+            validated_quality,
+            params.xspf_minimal,
+            params.xspf_compact,
+            params.edit,
+            params.strict_quality,
+            &params.player,
+        )?;
     } else {
+        let query_info = query_info.unwrap_or_else(|| combined_query_info(&filtered_results));
+        let mut out = open_output(params.output.as_deref())?;
         match params.format.as_str() {
             "json" => {
-                print_json(&filtered_results)?;
+                print_json(
+                    &mut out,
+                    &filtered_results,
+                    meta.as_ref().map(|meta| (meta, &stats)),
+                    params.compact,
+                )?;
+            }
+            "ndjson" => {
+                print_ndjson(&mut out, &filtered_results)?;
+            }
+            "yaml" => {
+                print_yaml(
+                    &mut out,
+                    &filtered_results,
+                    meta.as_ref().map(|meta| (meta, &stats)),
+                )?;
             }
             "csv" => {
-                print_csv(&filtered_results);
+                print_csv(&mut out, &filtered_results, params.csv_bom)?;
             }
             "xspf" => {
                 if params.xspf_file {
-                    save_xspf_playlist(&filtered_results, &params.query_terms)?;
+                    save_xspf_playlist(
+                        &filtered_results,
+                        &params.query_terms,
+                        params.xspf_minimal,
+                        params.xspf_compact,
+                        params.edit,
+                    )?;
                 } else {
-                    print_xspf(&filtered_results, &params.query_terms.join(" "));
+                    print_xspf(
+                        &mut out,
+                        &filtered_results,
+                        &params.query_terms.join(" "),
+                        params.xspf_minimal,
+                        params.xspf_compact,
+                    )?;
                 }
             }
             "oneline" => {
-                print_oneline(&filtered_results);
+                print_oneline(&mut out, &filtered_results, params.max_title_length)?;
             }
             "onelinetheme" => {
-                print_oneline_theme(&filtered_results);
+                print_oneline_theme(&mut out, &filtered_results, params.max_title_length)?;
             }
             "theme-count" => {
-                print_theme_count_table(&filtered_results);
+                print_theme_count_table(&mut out, &filtered_results, params.percent)?;
+            }
+            "rss" => {
+                print_rss(&mut out, &filtered_results, &params.query_terms.join(" "))?;
+            }
+            "atom" => {
+                print_atom(&mut out, &filtered_results, &params.query_terms.join(" "))?;
+            }
+            "jsonl-urls" => {
+                print_jsonl_urls(
+                    &mut out,
+                    &filtered_results,
+                    validate_quality(&params.quality),
+                )?;
+            }
+            "schedule" => {
+                print_schedule(&mut out, &filtered_results)?;
+            }
+            "vtt-index" => {
+                print_vtt_index(&mut out, &filtered_results)?;
+            }
+            "brief" => {
+                print_brief(&mut out, &filtered_results)?;
+            }
+            "template" => {
+                print_template(&mut out, &filtered_results, params.template.as_ref())?;
+            }
+            "id3-csv" => {
+                print_id3_csv(&mut out, &filtered_results, params.csv_bom)?;
+            }
+            "plist" => {
+                print_plist(
+                    &mut out,
+                    &filtered_results,
+                    &params.query_terms.join(" "),
+                    validate_quality(&params.quality),
+                )?;
+            }
+            "html" => {
+                print_html(
+                    &mut out,
+                    &filtered_results,
+                    &query_info,
+                    &params.query_terms.join(" "),
+                    validate_quality(&params.quality),
+                )?;
             }
             _ => {
-                print_table(&filtered_results, &result.query_info);
+                print_table(
+                    &mut out,
+                    &filtered_results,
+                    &query_info,
+                    params.group_by_channel,
+                )?;
             }
         }
+        finish_output(out, params.output.as_deref(), filtered_results.len())?;
     }
 
+    timings.record("output", output_start.elapsed());
+    timings.report();
+
+    if params.fail_if_empty && filtered_results.is_empty() {
+        exit_empty();
+    }
+
+    Ok(())
+}
+
+/// Exit status for `--fail-if-empty` when no result survives filtering, apart
+/// from 1 for errors and 2 for invalid arguments
+const EMPTY_RESULTS_EXIT_CODE: i32 = 3;
+
+/// End the process with `EMPTY_RESULTS_EXIT_CODE`, once the (empty) output is written
+fn exit_empty() -> ! {
+    tracing::info!(
+        code = %EMPTY_RESULTS_EXIT_CODE,
+        "No results left after filtering, exiting (--fail-if-empty)"
+    );
+    std::process::exit(EMPTY_RESULTS_EXIT_CODE)
+}
+
+/// A `QueryInfo` for the merged multi-search results, which have no single API response
+fn combined_query_info(
+    results: &[mediathekviewweb::models::Item],
+) -> mediathekviewweb::models::QueryInfo {
+    mediathekviewweb::models::QueryInfo {
+        filmliste_timestamp: 0,
+        result_count: results.len(),
+        search_engine_time: std::time::Duration::from_millis(0),
+        total_results: results.len() as u64,
+    }
+}
+
+/// Cache key for one API request: the query plus every parameter sent with it
+fn search_cache_key(query_string: &str, params: &SearchParams) -> String {
+    SearchCache::key(&[
+        query_string,
+        &params.size.to_string(),
+        &params.offset.to_string(),
+        &params.sort_by,
+        &params.sort_order,
+        &params.exclude_future.to_string(),
+        &params.all.to_string(),
+    ])
+}
+
+/// Note on stderr that results came from the cache, so they may be slightly stale
+fn report_cache_hit(age_secs: i64) {
+    eprintln!(
+        "{}",
+        format!(
+            "(cached) results from {}s ago, --no-cache to refresh",
+            age_secs
+        )
+        .dimmed()
+    );
+}
+
+/// Most results `--all` collects per query, so a broad query can't page through the whole film list
+const ALL_RESULTS_CAP: usize = 10_000;
+
+/// Fetch the pages after the first one until `total_results` are collected (`--all`)
+///
+/// `fetch_page` requests the page at an offset. Paging stops early on an empty
+/// page or at `ALL_RESULTS_CAP`, which is reported with a warning.
+async fn fetch_remaining_pages<F, Fut>(
+    results: &mut Vec<mediathekviewweb::models::Item>,
+    total_results: u64,
+    offset: u32,
+    fetch_page: F,
+) -> Result<()>
+where
+    F: Fn(u32) -> Fut,
+    Fut: std::future::Future<Output = Result<Vec<mediathekviewweb::models::Item>>>,
+{
+    let total = usize::try_from(total_results).unwrap_or(usize::MAX);
+    let mut next_offset = offset as usize + results.len();
+    let mut pages = 1;
+
+    while next_offset < total && results.len() < ALL_RESULTS_CAP && !results.is_empty() {
+        tracing::debug!(offset = %next_offset, total_results = %total, "Fetching next page");
+        let Ok(page_offset) = u32::try_from(next_offset) else {
+            break;
+        };
+        let page = fetch_page(page_offset).await?;
+        if page.is_empty() {
+            break;
+        }
+        next_offset += page.len();
+        results.extend(page);
+        pages += 1;
+    }
+
+    if results.len() > ALL_RESULTS_CAP || (results.len() == ALL_RESULTS_CAP && next_offset < total)
+    {
+        results.truncate(ALL_RESULTS_CAP);
+        eprintln!(
+            "{}",
+            format!(
+                "⚠️  --all stopped after {} of {} results. Narrow the query, or continue with --offset {}.",
+                ALL_RESULTS_CAP,
+                total,
+                offset as usize + ALL_RESULTS_CAP
+            )
+            .yellow()
+        );
+    }
+
+    tracing::info!(pages = %pages, results = %results.len(), total_results = %total, "Fetched all pages");
+
     Ok(())
 }
 
+/// Clean up query terms mangled by shell quoting
+///
+/// Trims whitespace and strips matching surrounding quotes, so `"Tatort "` or
+/// `'Tatort'` search exactly like `Tatort`. Terms left empty are dropped.
+fn normalize_query_terms(terms: Vec<String>) -> Vec<String> {
+    terms
+        .into_iter()
+        .map(|term| {
+            let mut term = term.trim();
+            while term.len() >= 2
+                && ((term.starts_with('"') && term.ends_with('"'))
+                    || (term.starts_with('\'') && term.ends_with('\'')))
+            {
+                term = term[1..term.len() - 1].trim();
+            }
+            term.to_string()
+        })
+        .filter(|term| !term.is_empty())
+        .collect()
+}
+
 async fn multi_search_content(client: &Mediathek, params: SearchParams) -> Result<()> {
     use std::collections::HashSet;
 
@@ -425,91 +1739,144 @@ async fn multi_search_content(client: &Mediathek, params: SearchParams) -> Resul
         "Starting multi-search mode"
     );
 
+    let mut timings = PhaseTimings::new(params.time);
+
     let mut all_results = Vec::new();
     let mut seen_urls = HashSet::new(); // For deduplication
+    let mut term_stats = Vec::new();
 
-    // Perform separate search for each query term
-    for (index, query_term) in params.query_terms.iter().enumerate() {
-        tracing::info!(
-            query_term = %query_term,
-            search_index = %(index + 1),
-            total_searches = %params.query_terms.len(),
-            "Executing individual search"
-        );
+    let cache = SearchCache::open(params.cache_ttl);
 
-        // Create params for individual search
-        let individual_params = SearchParams {
-            query_terms: vec![query_term.clone()],
-            exclude_patterns: params.exclude_patterns.clone(),
-            include_patterns: params.include_patterns.clone(),
-            size: params.size,
-            offset: params.offset,
-            sort_by: params.sort_by.clone(),
-            sort_order: params.sort_order.clone(),
-            exclude_future: params.exclude_future,
-            format: params.format.clone(),
-            vlc: params.vlc.clone(),
-            vlc_ai: params.vlc_ai,
-            xspf_file: params.xspf_file,
-            count: params.count,
-        };
+    // Perform a separate search for each query term, up to `--concurrency` at a time
+    let requests = params
+        .query_terms
+        .iter()
+        .enumerate()
+        .map(|(index, query_term)| {
+            let params = &params;
+            let cache = &cache;
+            async move {
+                tracing::info!(
+                    query_term = %query_term,
+                    search_index = %(index + 1),
+                    total_searches = %params.query_terms.len(),
+                    "Executing individual search"
+                );
 
-        // Perform individual search
-        let query_string = query_term.clone();
-        let (search_terms_only, duration_filters) = extract_duration_selectors(&query_string);
+                let query_string = apply_default_channels(query_term, &params.default_channels);
+                let (search_terms_only, duration_range) = extract_duration_selectors(&query_string);
+
+                let build_query = |offset: u32| {
+                    let mut query_builder = if search_terms_only.is_empty() {
+                        client.query_string("", false)
+                    } else {
+                        client.query_string(&search_terms_only, false)
+                    };
+
+                    // Apply duration filters
+                    if let Some(min_duration) = duration_range.min {
+                        query_builder = query_builder.duration_min(min_duration);
+                    }
+                    if let Some(max_duration) = duration_range.max {
+                        query_builder = query_builder.duration_max(max_duration);
+                    }
+
+                    // Apply other parameters
+                    query_builder = query_builder
+                        .include_future(!params.exclude_future)
+                        .size(params.size as usize)
+                        .offset(offset as usize);
+
+                    // Apply sorting
+                    let sort_field = match params.sort_by.as_str() {
+                        "duration" => SortField::Duration,
+                        "channel" => SortField::Channel,
+                        _ => SortField::Timestamp, // title and topic are sorted after the merge
+                    };
+
+                    let sort_direction = match params.sort_order.as_str() {
+                        "asc" => SortOrder::Ascending,
+                        _ => SortOrder::Descending,
+                    };
+
+                    query_builder.sort_by(sort_field).sort_order(sort_direction)
+                };
 
-        let mut query_builder = if search_terms_only.is_empty() {
-            client.query_string("", false)
-        } else {
-            client.query_string(&search_terms_only, false)
-        };
+                // Execute the query, or reuse a cached response
+                let request_start = Instant::now();
+                let cache_key = search_cache_key(&query_string, params);
+                if let Some(cached) = cache.as_ref().and_then(|cache| cache.load(&cache_key)) {
+                    report_cache_hit(cached.age_secs());
+                    let (results, query_info) = cached.into_parts();
+                    return anyhow::Ok((
+                        index,
+                        query_term,
+                        results,
+                        query_info,
+                        request_start.elapsed(),
+                    ));
+                }
 
-        // Apply duration filters
-        for filter in duration_filters {
-            if let Some(duration_str) = filter.strip_prefix('>') {
-                if let Ok(min_duration) = duration_str.parse::<u64>() {
-                    query_builder = query_builder
-                        .duration_min(std::time::Duration::from_secs(min_duration * 60));
+                let mut result =
+                    send_api_request(build_query(params.offset).send(), params.http.timeout)
+                        .await?;
+                if params.all {
+                    fetch_remaining_pages(
+                        &mut result.results,
+                        result.query_info.total_results,
+                        params.offset,
+                        |offset| {
+                            let query = build_query(offset);
+                            let timeout = params.http.timeout;
+                            async move {
+                                Ok(send_api_request(query.send(), timeout).await?.results)
+                            }
+                        },
+                    )
+                    .await?;
                 }
-            } else if let Some(duration_str) = filter.strip_prefix('<') {
-                if let Ok(max_duration) = duration_str.parse::<u64>() {
-                    query_builder = query_builder
-                        .duration_max(std::time::Duration::from_secs(max_duration * 60));
+                if let Some(cache) = cache {
+                    cache.store(&cache_key, &result.results, &result.query_info);
                 }
+                anyhow::Ok((
+                    index,
+                    query_term,
+                    result.results,
+                    result.query_info,
+                    request_start.elapsed(),
+                ))
             }
-        }
-
-        // Apply other parameters
-        query_builder = query_builder
-            .include_future(!individual_params.exclude_future)
-            .size(individual_params.size as usize)
-            .offset(individual_params.offset as usize);
-
-        // Apply sorting
-        let sort_field = match individual_params.sort_by.as_str() {
-            "duration" => SortField::Duration,
-            "channel" => SortField::Channel,
-            _ => SortField::Timestamp,
-        };
-
-        let sort_direction = match individual_params.sort_order.as_str() {
-            "asc" => SortOrder::Ascending,
-            _ => SortOrder::Descending,
-        };
+        });
 
-        query_builder = query_builder.sort_by(sort_field).sort_order(sort_direction);
+    let mut responses = futures::stream::iter(requests)
+        .buffer_unordered(params.concurrency)
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>>>()?;
+
+    // Responses arrive in completion order, merge them in term order so the
+    // deduplication below keeps the same items on every run
+    responses.sort_by_key(|(index, ..)| *index);
+
+    for (index, query_term, results, query_info, request_duration) in responses {
+        warn_on_result_anomalies(results.len(), &query_info, params.offset);
+        if index == 0 {
+            warn_on_stale_filmliste(&query_info);
+        }
 
-        // Execute the query
-        let result = query_builder.send().await?;
+        timings.record(format!("API request '{}'", query_term), request_duration);
 
         tracing::info!(
             query_term = %query_term,
-            result_count = %result.results.len(),
+            result_count = %results.len(),
             "Search completed"
         );
 
+        term_stats.push(TermStats::new(query_term, &query_info));
+
         // Add results with deduplication based on URL
-        for item in result.results {
+        for item in results {
             if seen_urls.insert(item.url_video.clone()) {
                 all_results.push(item);
             }
@@ -521,554 +1888,1904 @@ async fn multi_search_content(client: &Mediathek, params: SearchParams) -> Resul
         "Multi-search completed"
     );
 
-    // Sort unified results according to specified sort parameters
-    all_results.sort_by(|a, b| {
-        match params.sort_by.as_str() {
-            "duration" => {
-                let duration_a = a.duration.map(|d| d.as_secs()).unwrap_or(0);
-                let duration_b = b.duration.map(|d| d.as_secs()).unwrap_or(0);
-                match params.sort_order.as_str() {
-                    "asc" => duration_a.cmp(&duration_b),
-                    _ => duration_b.cmp(&duration_a),
-                }
-            }
-            "channel" => match params.sort_order.as_str() {
-                "asc" => a.channel.cmp(&b.channel),
-                _ => b.channel.cmp(&a.channel),
-            },
-            _ => {
-                // timestamp (default)
-                match params.sort_order.as_str() {
-                    "asc" => a.timestamp.cmp(&b.timestamp),
-                    _ => b.timestamp.cmp(&a.timestamp),
-                }
-            }
-        }
-    });
+    let stats = SearchStats::multi(term_stats);
+    process_results(client, params, all_results, None, stats, timings).await
+}
 
-    // Apply client-side regex filters to unified results
-    // Save count before moving results
-    let original_count = all_results.len();
+/// Scope a query to the configured default channels
+///
+/// Prepends one `!channel` selector per configured channel (multiple channel
+/// selectors are OR-ed by the API) unless the query already contains an explicit
+/// channel selector.
+fn apply_default_channels(query: &str, default_channels: &[String]) -> String {
+    if default_channels.is_empty() || query.split_whitespace().any(|t| t.starts_with('!')) {
+        return query.to_string();
+    }
 
-    // Apply client-side regex filters
-    let filtered_results = apply_regex_filters(
-        all_results,
-        params.exclude_patterns,
-        params.include_patterns,
-    )?;
+    // Spaces inside a selector value are written as commas in MediathekView syntax
+    let selectors: Vec<String> = default_channels
+        .iter()
+        .map(|channel| {
+            format!(
+                "!{}",
+                channel.split_whitespace().collect::<Vec<_>>().join(",")
+            )
+        })
+        .collect();
 
-    if filtered_results.len() != original_count {
-        tracing::info!(
-            before_count = %original_count,
-            after_count = %filtered_results.len(),
-            "Results filtered by regex patterns"
+    tracing::debug!(channels = ?default_channels, "Applying default channels from config");
+
+    format!("{} {}", selectors.join(" "), query)
+        .trim()
+        .to_string()
+}
+
+/// Turn an error from the MediathekViewWeb API into a clean message with a hint
+///
+/// The API answers malformed queries with an `{"err": [...]}` payload which the
+/// crate surfaces as an error; network problems are reported separately so the
+/// user knows whether to fix the query or simply retry. The hint goes to stderr
+/// so it stays out of piped output; the error itself is reported by the caller.
+fn explain_api_error<E: std::fmt::Display>(error: E) -> anyhow::Error {
+    let message = error.to_string();
+    let lower = message.to_lowercase();
+
+    tracing::error!(error = %message, "MediathekView API request failed");
+
+    if lower.contains("connect")
+        || lower.contains("error sending request")
+        || lower.contains("proxy")
+        || lower.contains("timed out")
+        || lower.contains("timeout")
+        || lower.contains("dns")
+        || lower.contains("503")
+        || lower.contains("502")
+    {
+        eprintln!(
+            "{}",
+            "💡 The API could not be reached. Check your connection (and HTTPS_PROXY if set) and retry in a moment."
+                .yellow()
+        );
+    } else {
+        eprintln!(
+            "{}",
+            "💡 Check your query syntax (!channel #topic +title *description >duration <duration) or retry in a moment."
+                .yellow()
         );
     }
 
-    // Output results using the same logic as single search
-    if params.count {
-        println!("{}", filtered_results.len());
-    } else if params.vlc_ai {
-        let search_info = get_clipboard_content()?;
-        process_with_ai(&filtered_results, search_info.as_deref()).await?;
-    } else if let Some(quality) = params.vlc {
-        let validated_quality = match quality.as_str() {
-            "l" | "low" => "l",
-            "h" | "hd" | "high" => "h",
-            "m" | "medium" | "" => "m",
-            _ => {
-                println!("{}", format!("Warning: Invalid quality '{quality}'. Using medium quality (m). Valid options: l (low), m (medium), h (HD)").yellow());
-                "m"
-            }
-        };
-        create_vlc_playlist_and_launch(&filtered_results, &params.query_terms, validated_quality)?;
-    } else {
-        match params.format.as_str() {
-            "json" => {
-                print_json(&filtered_results)?;
-            }
-            "csv" => {
-                print_csv(&filtered_results);
-            }
-            "xspf" => {
-                if params.xspf_file {
-                    save_xspf_playlist(&filtered_results, &params.query_terms)?;
-                } else {
-                    print_xspf(&filtered_results, &params.query_terms.join(" "));
+    anyhow::anyhow!("MediathekViewWeb API error: {}", message)
+}
+
+/// Await an API request, failing it once `timeout` (from `--timeout`) has passed
+///
+/// Errors and timeouts are both reported through `explain_api_error`.
+async fn send_api_request<T, E: std::fmt::Display>(
+    request: impl std::future::Future<Output = std::result::Result<T, E>>,
+    timeout: Option<std::time::Duration>,
+) -> Result<T> {
+    let Some(timeout) = timeout else {
+        return request.await.map_err(explain_api_error);
+    };
+
+    match tokio::time::timeout(timeout, request).await {
+        Ok(result) => result.map_err(explain_api_error),
+        Err(_) => Err(explain_api_error(format!(
+            "request timed out after {}s (--timeout)",
+            timeout.as_secs()
+        ))),
+    }
+}
+
+/// Warn about result shapes that indicate a problem rather than "no matches"
+fn warn_on_result_anomalies(
+    returned: usize,
+    query_info: &mediathekviewweb::models::QueryInfo,
+    offset: u32,
+) {
+    let total = query_info.total_results;
+
+    if returned == 0 && total > 0 {
+        tracing::warn!(offset = %offset, total_results = %total, "Offset beyond available results");
+        eprintln!(
+            "{}",
+            format!(
+                "⚠️  Offset {} is beyond the {} available results. Use a smaller --offset.",
+                offset, total
+            )
+            .yellow()
+        );
+    } else if returned as u64 > total {
+        tracing::warn!(
+            returned = %returned,
+            total_results = %total,
+            "API returned more results than it reported"
+        );
+    }
+}
+
+/// Age after which the server's film list is considered stale
+const STALE_FILMLISTE_DAYS: i64 = 7;
+
+/// Warn when the server's film list is old, which means recent broadcasts are
+/// missing and links may be dead - usually the mirror stopped updating rather
+/// than the query being wrong
+fn warn_on_stale_filmliste(query_info: &mediathekviewweb::models::QueryInfo) {
+    if query_info.filmliste_timestamp <= 0 {
+        return;
+    }
+
+    let age_days = (chrono::Utc::now().timestamp() - query_info.filmliste_timestamp) / 86_400;
+    if age_days < STALE_FILMLISTE_DAYS {
+        return;
+    }
+
+    let updated = DateTime::from_timestamp(query_info.filmliste_timestamp, 0)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default();
+
+    tracing::warn!(
+        filmliste_timestamp = %query_info.filmliste_timestamp,
+        age_days = %age_days,
+        "Film list is stale"
+    );
+    eprintln!(
+        "{}",
+        format!(
+            "⚠️  The MediathekView film list was last updated {} ({} days ago). Recent content may be missing and links may be broken - the mirror may be down.",
+            updated, age_days
+        )
+        .yellow()
+    );
+}
+
+/// Duration bounds from the `>N`/`<N` selectors of a query
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct DurationRange {
+    min: Option<std::time::Duration>,
+    max: Option<std::time::Duration>,
+}
+
+/// Split the duration selectors off a query, returning the remaining search terms
+///
+/// A selector token is one or more `>N`/`<N` bounds, so `>60<120` works like
+/// `>60 <120`. `N` is in minutes unless it carries a unit: `90m`/`90min`,
+/// `5400s` or `2h`. A later bound of the same kind replaces an earlier one.
+/// Tokens that merely contain `>` or `<` (or overflow) stay search terms.
+fn extract_duration_selectors(query: &str) -> (String, DurationRange) {
+    let token_pattern = Regex::new(r"^(?:[<>]\d+(?:min|m|s|h)?)+$").unwrap();
+    let bound_pattern = Regex::new(r"([<>])(\d+)(min|m|s|h)?").unwrap();
+
+    let mut search_terms = Vec::new();
+    let mut range = DurationRange::default();
+
+    for token in query.split_whitespace() {
+        let bounds = token_pattern
+            .is_match(token)
+            .then(|| {
+                bound_pattern
+                    .captures_iter(token)
+                    .map(|bound| {
+                        let seconds_per_unit = match bound.get(3).map(|unit| unit.as_str()) {
+                            Some("s") => 1,
+                            Some("h") => 3600,
+                            _ => 60,
+                        };
+                        let seconds = bound[2]
+                            .parse::<u64>()
+                            .ok()?
+                            .checked_mul(seconds_per_unit)?;
+                        Some((
+                            bound[1].to_string(),
+                            std::time::Duration::from_secs(seconds),
+                        ))
+                    })
+                    .collect::<Option<Vec<_>>>()
+            })
+            .flatten();
+
+        match bounds {
+            Some(bounds) => {
+                for (kind, duration) in bounds {
+                    if kind == ">" {
+                        range.min = Some(duration);
+                    } else {
+                        range.max = Some(duration);
+                    }
                 }
             }
-            "oneline" => {
-                print_oneline(&filtered_results);
-            }
-            "onelinetheme" => {
-                print_oneline_theme(&filtered_results);
-            }
-            "theme-count" => {
-                print_theme_count_table(&filtered_results);
-            }
-            _ => {
-                // Create a mock QueryInfo for table display
-                let query_info = mediathekviewweb::models::QueryInfo {
-                    filmliste_timestamp: 0,
-                    result_count: filtered_results.len(),
-                    search_engine_time: std::time::Duration::from_millis(0),
-                    total_results: filtered_results.len() as u64,
-                };
-                print_table(&filtered_results, &query_info);
-            }
+            // Keep all other tokens (search terms and selectors) as-is
+            None => search_terms.push(token),
         }
     }
 
-    Ok(())
+    (search_terms.join(" "), range)
 }
 
-fn extract_duration_selectors(query: &str) -> (String, Vec<String>) {
-    // Check if query contains duration selectors (>X or <X patterns)
-    let duration_pattern = regex::Regex::new(r"[><]\d+").unwrap();
+/// Upper bound for the compiled size of a user-supplied filter regex
+const FILTER_REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+/// Compile a user-supplied `--include`/`--exclude` pattern, case-insensitive
+/// unless `case_sensitive`
+///
+/// `source` is the pattern as the user typed it (before ASCII folding) and
+/// `position` its 1-based place among the values of its flag, both named in
+/// the error so the broken one is easy to find among several.
+///
+/// The regex crate guarantees linear-time matching, so there is no
+/// catastrophic backtracking to time out on. What a pathological pattern can
+/// still do is explode in compiled size (e.g. large bounded repetitions), so
+/// the compiled program and lazy DFA are capped and such patterns rejected.
+fn compile_filter_regex(
+    pattern: &str,
+    source: &str,
+    kind: &str,
+    position: usize,
+    case_sensitive: bool,
+) -> Result<Regex> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(!case_sensitive)
+        .size_limit(FILTER_REGEX_SIZE_LIMIT)
+        .dfa_size_limit(FILTER_REGEX_SIZE_LIMIT)
+        .build()
+        .map_err(|e| match e {
+            regex::Error::CompiledTooBig(limit) => anyhow::anyhow!(
+                "{} pattern #{} '{}' is too complex (compiled size exceeds {} bytes), simplify the pattern",
+                kind,
+                position,
+                source,
+                limit
+            ),
+            e => anyhow::anyhow!("Invalid {} pattern #{} '{}': {}", kind, position, source, e),
+        })
+}
 
-    if !duration_pattern.is_match(query) {
-        // No duration selectors, return original query and empty filters
-        return (query.to_string(), Vec::new());
+/// Fold German umlauts and ß to ASCII, spelled out (ä→ae) or as the bare vowel (ä→a)
+///
+/// ß always becomes ss.
+fn ascii_fold(text: &str, spell_out: bool) -> String {
+    let mut folded = String::with_capacity(text.len());
+    for c in text.chars() {
+        let (bare, suffix) = match c {
+            'ä' => ('a', "e"),
+            'ö' => ('o', "e"),
+            'ü' => ('u', "e"),
+            'Ä' => ('A', "e"),
+            'Ö' => ('O', "e"),
+            'Ü' => ('U', "e"),
+            'ß' => ('s', "s"),
+            'ẞ' => ('S', "S"),
+            _ => {
+                folded.push(c);
+                continue;
+            }
+        };
+        folded.push(bare);
+        if spell_out || matches!(c, 'ß' | 'ẞ') {
+            folded.push_str(suffix);
+        }
     }
+    folded
+}
 
-    // Split query into tokens
-    let tokens: Vec<&str> = query.split_whitespace().collect();
-    let mut search_terms = Vec::new();
-    let mut duration_selectors = Vec::new();
+/// The spellings of a pattern or text that filters compare
+///
+/// With `fold` both ASCII spellings of umlauts, the first matched against the
+/// first: "Käthe" then matches "Kaethe" as well as "Kathe".
+fn filter_spellings(text: &str, fold: bool) -> Vec<String> {
+    if fold {
+        vec![ascii_fold(text, true), ascii_fold(text, false)]
+    } else {
+        vec![text.to_string()]
+    }
+}
 
-    for token in tokens {
-        if duration_pattern.is_match(token) {
-            duration_selectors.push(token.to_string());
-        } else {
-            // Keep all other tokens (search terms and selectors) as-is
-            search_terms.push(token);
-        }
+/// A filter pattern compiled once per spelling from `filter_spellings`
+struct FilterRegex(Vec<Regex>);
+
+impl FilterRegex {
+    fn new(
+        pattern: &str,
+        kind: &str,
+        position: usize,
+        fold: bool,
+        case_sensitive: bool,
+    ) -> Result<Self> {
+        filter_spellings(pattern, fold)
+            .iter()
+            .map(|spelling| compile_filter_regex(spelling, pattern, kind, position, case_sensitive))
+            .collect::<Result<Vec<Regex>>>()
+            .map(FilterRegex)
+    }
+
+    /// Whether any spelling of the pattern matches the same spelling of the text
+    fn is_match(&self, spellings: &[String]) -> bool {
+        self.0
+            .iter()
+            .zip(spellings)
+            .any(|(regex, text)| regex.is_match(text))
     }
+}
 
-    // Return search terms and duration filters separately
-    let search_query = search_terms.join(" ");
-    (search_query, duration_selectors)
+/// Spellings of the result text that include/exclude patterns are matched against
+fn regex_filter_text(entry: &mediathekviewweb::models::Item, fold: bool) -> Vec<String> {
+    let combined_text = [
+        entry.channel.as_str(),
+        &entry.topic,
+        &entry.title,
+        entry.description.as_deref().unwrap_or(""),
+    ]
+    .join(" ");
+
+    filter_spellings(&combined_text, fold)
 }
 
 fn apply_regex_filters(
     results: Vec<mediathekviewweb::models::Item>,
     exclude_patterns: Option<Vec<String>>,
     include_patterns: Option<Vec<String>>,
+    fold: bool,
+    case_sensitive: bool,
+    stats: bool,
 ) -> Result<Vec<mediathekviewweb::models::Item>> {
     let mut filtered_results = results;
 
     // Apply exclude regex patterns
     if let Some(exclude_terms) = exclude_patterns {
         if !exclude_terms.is_empty() {
-            let exclude_regexes: Result<Vec<Regex>, _> = exclude_terms
+            let exclude_regexes = exclude_terms
                 .iter()
-                .map(|pattern| Regex::new(&format!("(?i){pattern}")))
-                .collect();
-
-            let exclude_regexes =
-                exclude_regexes.map_err(|e| anyhow::anyhow!("Invalid exclude regex: {}", e))?;
-
+                .enumerate()
+                .map(|(index, pattern)| {
+                    FilterRegex::new(pattern, "exclude", index + 1, fold, case_sensitive)
+                })
+                .collect::<Result<Vec<FilterRegex>>>()?;
+
+            // Every pattern is checked, not just up to the first hit, so the
+            // per-pattern counts are exact
+            let mut removed = vec![0usize; exclude_regexes.len()];
             filtered_results.retain(|entry| {
-                let text_fields = [
-                    entry.channel.as_str(),
-                    &entry.topic,
-                    &entry.title,
-                    entry.description.as_deref().unwrap_or(""),
-                ];
-
-                let combined_text = text_fields.join(" ");
+                let combined_text = regex_filter_text(entry, fold);
 
                 // Return true (keep) if none of the exclude patterns match
-                !exclude_regexes
-                    .iter()
-                    .any(|pattern| pattern.is_match(&combined_text))
+                let mut keep = true;
+                for (index, pattern) in exclude_regexes.iter().enumerate() {
+                    if pattern.is_match(&combined_text) {
+                        removed[index] += 1;
+                        keep = false;
+                    }
+                }
+                keep
             });
+
+            if stats {
+                for (pattern, count) in exclude_terms.iter().zip(&removed) {
+                    eprintln!("📊 exclude '{}': removed {}", pattern, count);
+                }
+            }
         }
     }
 
     // Apply include regex patterns
     if let Some(include_terms) = include_patterns {
         if !include_terms.is_empty() {
-            let include_regexes: Result<Vec<Regex>, _> = include_terms
+            let include_regexes = include_terms
                 .iter()
-                .map(|pattern| Regex::new(&format!("(?i){pattern}")))
-                .collect();
-
-            let include_regexes =
-                include_regexes.map_err(|e| anyhow::anyhow!("Invalid include regex: {}", e))?;
+                .enumerate()
+                .map(|(index, pattern)| {
+                    FilterRegex::new(pattern, "include", index + 1, fold, case_sensitive)
+                })
+                .collect::<Result<Vec<FilterRegex>>>()?;
 
+            let mut kept = vec![0usize; include_regexes.len()];
             filtered_results.retain(|entry| {
-                let text_fields = [
-                    entry.channel.as_str(),
-                    &entry.topic,
-                    &entry.title,
-                    entry.description.as_deref().unwrap_or(""),
-                ];
-
-                let combined_text = text_fields.join(" ");
+                let combined_text = regex_filter_text(entry, fold);
 
                 // Return true (keep) if any of the include patterns match
-                include_regexes
-                    .iter()
-                    .any(|pattern| pattern.is_match(&combined_text))
+                let mut keep = false;
+                for (index, pattern) in include_regexes.iter().enumerate() {
+                    if pattern.is_match(&combined_text) {
+                        kept[index] += 1;
+                        keep = true;
+                    }
+                }
+                keep
             });
+
+            if stats {
+                for (pattern, count) in include_terms.iter().zip(&kept) {
+                    eprintln!("📊 include '{}': kept {}", pattern, count);
+                }
+            }
         }
     }
 
     Ok(filtered_results)
 }
 
-async fn list_channels(client: &Mediathek) -> Result<()> {
-    // Get channels by making a wildcard query and extracting unique channels
-    let result = client.query_string("", true).size(1000).send().await?;
-    let mut channels: Vec<String> = result
-        .results
-        .iter()
-        .map(|item| item.channel.clone())
-        .collect();
-    channels.sort();
-    channels.dedup();
+/// A result field a regex filter can be scoped to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum FilterField {
+    Channel,
+    Topic,
+    Title,
+    Description,
+}
 
-    println!("{}", "Available Channels:".bold().blue());
-    println!();
+impl FilterField {
+    fn name(self) -> &'static str {
+        match self {
+            FilterField::Channel => "channel",
+            FilterField::Topic => "topic",
+            FilterField::Title => "title",
+            FilterField::Description => "description",
+        }
+    }
 
-    for (i, channel) in channels.iter().enumerate() {
-        if i % 4 == 0 && i > 0 {
-            println!();
+    /// The `--include-*`/`--exclude-*` flag for this field, without dashes
+    ///
+    /// Topic and channel take a `-regex` suffix, their plain flags are exact matches.
+    fn flag(self, kind: &str) -> String {
+        match self {
+            FilterField::Channel | FilterField::Topic => format!("{}-{}-regex", kind, self.name()),
+            FilterField::Title | FilterField::Description => format!("{}-{}", kind, self.name()),
         }
-        print!("{:<20}", channel.green());
     }
-    println!();
-    println!();
-    println!(
-        "{}: Use {} to filter by channel",
-        "Tip".yellow(),
-        "!CHANNEL".cyan()
-    );
-    println!(
-        "{}: Use {} for duration filtering",
-        "Tip".yellow(),
-        ">90 <120".cyan()
-    );
 
-    Ok(())
+    fn text(self, entry: &mediathekviewweb::models::Item) -> &str {
+        match self {
+            FilterField::Channel => &entry.channel,
+            FilterField::Topic => &entry.topic,
+            FilterField::Title => &entry.title,
+            FilterField::Description => entry.description.as_deref().unwrap_or(""),
+        }
+    }
 }
 
-fn create_vlc_playlist_and_launch(
-    results: &[mediathekviewweb::models::Item],
-    query_terms: &[String],
-    quality: &str,
-) -> Result<()> {
-    if results.is_empty() {
-        println!("{}", "No results found to add to playlist.".yellow());
-        return Ok(());
+/// A regex filter that only looks at one field, from `--include-title` and friends
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FieldPattern {
+    field: FilterField,
+    pattern: String,
+}
+
+impl FieldPattern {
+    /// Flatten the per-field flag values into one list
+    fn collect(fields: &[(FilterField, Vec<String>)]) -> Vec<Self> {
+        fields
+            .iter()
+            .flat_map(|(field, patterns)| {
+                patterns.iter().map(|pattern| FieldPattern {
+                    field: *field,
+                    pattern: pattern.clone(),
+                })
+            })
+            .collect()
     }
+}
 
-    // Create playlist filename from query (now XSPF)
-    let playlist_name = generate_vlc_playlist_filename(&query_terms.join(" "));
+/// Apply the field-scoped regex filters
+///
+/// Unlike `--include`, which keeps a result when any pattern matches anywhere,
+/// every field with include patterns must match one of its own patterns, so
+/// `--include-title X --include-topic-regex Y` needs both. A result is dropped when
+/// any exclude pattern matches its field.
+fn apply_field_regex_filters(
+    results: Vec<mediathekviewweb::models::Item>,
+    include_fields: &[FieldPattern],
+    exclude_fields: &[FieldPattern],
+    fold: bool,
+    case_sensitive: bool,
+    stats: bool,
+) -> Result<Vec<mediathekviewweb::models::Item>> {
+    if include_fields.is_empty() && exclude_fields.is_empty() {
+        return Ok(results);
+    }
 
-    // Generate XSPF content
-    let xspf_content = generate_xspf_content(results, &query_terms.join(" "), quality);
+    let compile =
+        |patterns: &[FieldPattern], kind: &str| -> Result<Vec<(FilterField, FilterRegex)>> {
+            patterns
+                .iter()
+                .enumerate()
+                .map(|(index, field_pattern)| {
+                    // Counted per flag, `--include-title a --include-topic-regex b` are both #1
+                    let position = patterns[..index]
+                        .iter()
+                        .filter(|other| other.field == field_pattern.field)
+                        .count()
+                        + 1;
+                    let kind = field_pattern.field.flag(kind);
+                    Ok((
+                        field_pattern.field,
+                        FilterRegex::new(
+                            &field_pattern.pattern,
+                            &kind,
+                            position,
+                            fold,
+                            case_sensitive,
+                        )?,
+                    ))
+                })
+                .collect()
+        };
+    let includes = compile(include_fields, "include")?;
+    let excludes = compile(exclude_fields, "exclude")?;
 
-    // Write to file
-    let mut file = File::create(&playlist_name)?;
-    writeln!(file, "{xspf_content}")?;
+    let field_text = |entry: &mediathekviewweb::models::Item, field: FilterField| {
+        filter_spellings(field.text(entry), fold)
+    };
 
-    println!(
-        "{}",
-        format!("Created XSPF playlist: {playlist_name}").green()
-    );
-    println!(
-        "{}",
-        format!("Added {} video(s) to playlist", results.len()).green()
-    );
+    let mut removed = vec![0usize; excludes.len()];
+    let mut kept = vec![0usize; includes.len()];
+    let mut filtered_results = results;
+    filtered_results.retain(|entry| {
+        let mut keep = true;
+        for (index, (field, regex)) in excludes.iter().enumerate() {
+            if regex.is_match(&field_text(entry, *field)) {
+                removed[index] += 1;
+                keep = false;
+            }
+        }
 
-    // Try to launch VLC with the playlist
-    println!("{}", "Launching VLC...".yellow());
-
-    let vlc_result = if cfg!(target_os = "windows") {
-        // Try common VLC paths on Windows
-        Command::new("vlc")
-            .arg(&playlist_name)
-            .spawn()
-            .or_else(|_| {
-                Command::new("C:\\Program Files\\VideoLAN\\VLC\\vlc.exe")
-                    .arg(&playlist_name)
-                    .spawn()
-            })
-            .or_else(|_| {
-                Command::new("C:\\Program Files (x86)\\VideoLAN\\VLC\\vlc.exe")
-                    .arg(&playlist_name)
-                    .spawn()
-            })
-    } else {
-        // Try VLC on Unix-like systems
-        Command::new("vlc").arg(&playlist_name).spawn()
-    };
+        let mut matched_fields = Vec::new();
+        for (index, (field, regex)) in includes.iter().enumerate() {
+            if regex.is_match(&field_text(entry, *field)) {
+                kept[index] += 1;
+                matched_fields.push(*field);
+            }
+        }
+        keep && includes
+            .iter()
+            .all(|(field, _)| matched_fields.contains(field))
+    });
 
-    match vlc_result {
-        Ok(_) => {
-            println!("{}", "VLC launched successfully!".green());
+    if stats {
+        for (field_pattern, count) in exclude_fields.iter().zip(&removed) {
+            eprintln!(
+                "📊 exclude {} '{}': removed {}",
+                field_pattern.field.name(),
+                field_pattern.pattern,
+                count
+            );
         }
-        Err(e) => {
-            println!("{}", format!("Failed to launch VLC: {e}").red());
-            println!("{}", format!("Playlist saved as: {playlist_name}").yellow());
-            println!("{}", "You can manually open this file with VLC.".yellow());
+        for (field_pattern, count) in include_fields.iter().zip(&kept) {
+            eprintln!(
+                "📊 include {} '{}': matched {}",
+                field_pattern.field.name(),
+                field_pattern.pattern,
+                count
+            );
         }
     }
 
-    Ok(())
+    Ok(filtered_results)
 }
 
-fn generate_vlc_playlist_filename(query: &str) -> String {
-    // Sanitize the query for use as filename
-    let sanitized = query
-        .chars()
-        .map(|c| match c {
-            'a'..='z' | 'A'..='Z' | '0'..='9' => c,
-            '>' | '<' => 'm', // Convert > to "more", < to "less" indicator
-            _ => '_',         // includes spaces and all other characters
-        })
-        .collect::<String>()
-        .trim_matches('_')
-        .to_string();
+/// Keep or drop results by exact (case-insensitive) topic match
+///
+/// More precise than a regex over the combined text, which might also match
+/// the same word in a title or description.
+fn apply_topic_filters(
+    results: Vec<mediathekviewweb::models::Item>,
+    topics: &[String],
+    exclude_topics: &[String],
+) -> Vec<mediathekviewweb::models::Item> {
+    if topics.is_empty() && exclude_topics.is_empty() {
+        return results;
+    }
 
-    // Limit filename length and add timestamp suffix for uniqueness
-    let max_len = 50;
-    let truncated = if sanitized.len() > max_len {
-        let partial = &sanitized[..max_len];
-        format!("{partial}...")
-    } else {
-        sanitized
+    let normalize = |topics: &[String]| -> Vec<String> {
+        topics.iter().map(|t| t.trim().to_lowercase()).collect()
     };
+    let topics = normalize(topics);
+    let exclude_topics = normalize(exclude_topics);
+
+    let before_count = results.len();
+    let filtered: Vec<_> = results
+        .into_iter()
+        .filter(|entry| {
+            let topic = entry.topic.trim().to_lowercase();
+            (topics.is_empty() || topics.contains(&topic)) && !exclude_topics.contains(&topic)
+        })
+        .collect();
 
-    // Add short timestamp to avoid conflicts
-    let timestamp = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs()
-        % 10000; // Last 4 digits
+    if filtered.len() != before_count {
+        tracing::info!(
+            before_count = %before_count,
+            after_count = %filtered.len(),
+            "Results filtered by topic"
+        );
+    }
 
-    format!("mwb_{truncated}_{timestamp}.xspf")
+    filtered
 }
 
-async fn process_with_ai(
-    results: &[mediathekviewweb::models::Item],
-    search_info: Option<&str>,
-) -> Result<()> {
-    if results.is_empty() {
-        println!("{}", "No results found to process with AI.".yellow());
-        return Ok(());
+/// Compare a result's channel with a user-supplied channel name
+///
+/// Case-insensitive by default; `--channel-case-exact` switches to exact
+/// comparison for scripted pipelines that need to distinguish similar names.
+fn channel_matches(channel: &str, name: &str, case_exact: bool) -> bool {
+    if case_exact {
+        channel == name
+    } else {
+        channel.to_lowercase() == name.to_lowercase()
     }
+}
 
-    // Load environment variables from .env file if it exists
-    dotenvy::dotenv().ok();
-
-    println!("{}", "🚀 Initializing Gemini AI processor...".yellow());
+/// Keep results from the `--channel` allow list (if any) and drop excluded channels
+///
+/// A channel on both lists is dropped.
+fn apply_channel_filters(
+    results: Vec<mediathekviewweb::models::Item>,
+    channels: &[String],
+    exclude_channels: &[String],
+    case_exact: bool,
+) -> Vec<mediathekviewweb::models::Item> {
+    if channels.is_empty() && exclude_channels.is_empty() {
+        return results;
+    }
 
-    let processor = match AIProcessor::new_with_verbose(search_info).await {
-        Ok(processor) => processor,
-        Err(e) => {
-            println!(
-                "{}",
-                format!("❌ Failed to initialize AI processor: {}", e).red()
-            );
-            println!(
-                "{}",
-                "💡 Make sure you have set GOOGLE_API_KEY in your environment or .env file"
-                    .yellow()
-            );
-            println!(
-                "{}",
-                "   You can get an API key from: https://aistudio.google.com/app/apikey".cyan()
-            );
-            return Ok(());
-        }
+    let listed = |names: &[String], channel: &str| {
+        names
+            .iter()
+            .any(|name| channel_matches(channel, name.trim(), case_exact))
     };
 
-    match processor.process_episodes(results).await {
-        Ok(response) => {
-            println!("\n{}", "✅ AI Processing Results:".green().bold());
-            println!("{}", "=".repeat(50).green());
-            println!("{}", response);
-            println!("{}", "=".repeat(50).green());
+    let before_count = results.len();
+    let filtered: Vec<_> = results
+        .into_iter()
+        .filter(|entry| {
+            (channels.is_empty() || listed(channels, &entry.channel))
+                && !listed(exclude_channels, &entry.channel)
+        })
+        .collect();
 
-            // Optionally save the results to a file
-            let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
-            let filename = format!("ai_sorted_episodes_{}.txt", timestamp);
+    if filtered.len() != before_count {
+        tracing::info!(
+            before_count = %before_count,
+            after_count = %filtered.len(),
+            case_exact = %case_exact,
+            "Results filtered by channel"
+        );
+    }
 
-            if let Ok(mut file) = File::create(&filename) {
-                writeln!(
-                    file,
-                    "AI Sorted Episodes - Generated on {}",
-                    chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC")
-                )?;
-                writeln!(file, "{}", "=".repeat(70))?;
-                writeln!(file, "{}", response)?;
-                println!("\n{}", format!("📄 Results saved to: {}", filename).cyan());
-            }
-        }
-        Err(e) => {
-            let error_msg = e.to_string().to_lowercase();
+    filtered
+}
 
-            if error_msg.contains("401")
-                || error_msg.contains("unauthorized")
-                || error_msg.contains("api key")
-            {
-                println!("{}", "🔑 API Key Issue Detected!".yellow().bold());
-                println!();
-                println!("{}", "❌ There's a problem with your Google API key.".red());
-                println!();
-                println!("{}", "💡 To fix this:".cyan().bold());
-                println!(
-                    "{}",
-                    "   1. Visit: https://aistudio.google.com/app/u/5/apikey".cyan()
-                );
-                println!("{}", "   2. Generate a new API key if needed".cyan());
-                println!(
-                    "{}",
-                    "   3. Copy the key to your .env file as GOOGLE_API_KEY=your_key_here".cyan()
-                );
-                println!();
-                println!("{}", "🌐 Opening API key page in your browser...".green());
+/// Drop results without a real synopsis
+///
+/// Descriptions are trimmed and measured in characters, so umlauts count once.
+/// Missing descriptions count as empty.
+fn apply_description_length_filter(
+    results: Vec<mediathekviewweb::models::Item>,
+    min_length: Option<usize>,
+) -> Vec<mediathekviewweb::models::Item> {
+    let Some(min_length) = min_length else {
+        return results;
+    };
 
-                // Try to open the API key page in browser
-                let url = "https://aistudio.google.com/app/u/5/apikey";
-                let _ = open_browser_url(url);
-            } else if error_msg.contains("429")
-                || error_msg.contains("quota")
-                || error_msg.contains("rate limit")
-            {
-                println!("{}", "⏱️  API Quota/Rate Limit Exceeded!".yellow().bold());
-                println!();
-                println!("{}", "❌ You've exceeded the API quota limits.".red());
-                println!();
-                println!("{}", "💡 Solutions:".cyan().bold());
-                println!("{}", "   1. Wait a few minutes and try again".cyan());
-                println!(
-                    "{}",
-                    "   2. Check your quota limits at the API console".cyan()
-                );
-                println!(
-                    "{}",
-                    "   3. Consider upgrading to a paid plan for higher limits".cyan()
-                );
-                println!();
-                println!(
-                    "{}",
-                    "🌐 Opening Google AI Studio to check your usage...".green()
-                );
+    let before_count = results.len();
+    let filtered: Vec<_> = results
+        .into_iter()
+        .filter(|entry| {
+            entry
+                .description
+                .as_deref()
+                .map_or(0, |d| d.trim().chars().count())
+                >= min_length
+        })
+        .collect();
 
-                let url = "https://aistudio.google.com/app/u/5/apikey";
-                let _ = open_browser_url(url);
-            } else {
-                println!("{}", format!("❌ AI processing failed: {}", e).red());
-                println!("{}", "💡 The AI might need more specific episode information or the search tools might be having issues".yellow());
-            }
-        }
+    if filtered.len() != before_count {
+        tracing::info!(
+            before_count = %before_count,
+            after_count = %filtered.len(),
+            min_length = %min_length,
+            "Results filtered by description length"
+        );
     }
 
-    Ok(())
+    filtered
 }
 
-/// Open URL in the default browser
-fn open_browser_url(url: &str) -> Result<()> {
-    #[cfg(target_os = "windows")]
-    {
-        Command::new("cmd").args(["/C", "start", url]).spawn()?;
+/// Keep results aired on or after `after` and before `before`
+///
+/// Days start at 00:00 UTC, the same time zone the output formats show.
+fn apply_date_range_filter(
+    results: Vec<mediathekviewweb::models::Item>,
+    after: Option<chrono::NaiveDate>,
+    before: Option<chrono::NaiveDate>,
+) -> Vec<mediathekviewweb::models::Item> {
+    if after.is_none() && before.is_none() {
+        return results;
     }
 
-    #[cfg(target_os = "macos")]
-    {
-        Command::new("open").arg(url).spawn()?;
-    }
+    let day_start =
+        |date: chrono::NaiveDate| date.and_time(chrono::NaiveTime::MIN).and_utc().timestamp();
+    let min_timestamp = after.map(day_start).unwrap_or(i64::MIN);
+    let max_timestamp = before.map(day_start).unwrap_or(i64::MAX);
 
-    #[cfg(target_os = "linux")]
-    {
-        Command::new("xdg-open").arg(url).spawn()?;
+    let before_count = results.len();
+    let filtered: Vec<_> = results
+        .into_iter()
+        .filter(|entry| (min_timestamp..max_timestamp).contains(&entry.timestamp))
+        .collect();
+
+    if filtered.len() != before_count {
+        tracing::info!(
+            before_count = %before_count,
+            after_count = %filtered.len(),
+            after = ?after,
+            before = ?before,
+            "Results filtered by date range"
+        );
     }
 
-    Ok(())
+    filtered
 }
 
-fn print_table(
-    results: &[mediathekviewweb::models::Item],
-    query_info: &mediathekviewweb::models::QueryInfo,
-) {
-    println!("{}", "Search Results".bold().blue());
-    println!(
-        "Total results: {}",
-        query_info.total_results.to_string().green()
-    );
-    println!("Showing: {}", query_info.result_count.to_string().green());
-    let search_time = query_info.search_engine_time.as_millis();
-    println!("Search time: {}ms", format!("{search_time:.2}").yellow());
-    println!();
-
-    if results.is_empty() {
-        println!("{}", "No results found.".yellow());
-        return;
+/// Whether an entry is a livestream rather than an on-demand video
+///
+/// The API has no dedicated field, so this goes by the markers the broadcasters
+/// use: "Livestream" in topic or title, or a standalone "live" word on an entry
+/// without a duration.
+fn is_livestream(entry: &mediathekviewweb::models::Item) -> bool {
+    let text = format!("{} {}", entry.topic, entry.title).to_lowercase();
+    if text.contains("livestream") {
+        return true;
     }
 
-    for (i, entry) in results.iter().enumerate() {
-        let entry_num = i + 1;
-        println!(
-            "{} {}",
-            format!("{entry_num}.").blue().bold(),
-            "─".repeat(60).blue()
+    let has_duration = entry.duration.is_some_and(|d| !d.is_zero());
+    !has_duration
+        && text
+            .split(|c: char| !c.is_alphanumeric())
+            .any(|word| word == "live")
+}
+
+/// Drop livestream entries (see `is_livestream`)
+fn drop_livestreams(
+    results: Vec<mediathekviewweb::models::Item>,
+) -> Vec<mediathekviewweb::models::Item> {
+    let before_count = results.len();
+    let filtered: Vec<_> = results
+        .into_iter()
+        .filter(|entry| !is_livestream(entry))
+        .collect();
+
+    if filtered.len() != before_count {
+        tracing::info!(
+            before_count = %before_count,
+            after_count = %filtered.len(),
+            "Livestreams filtered out"
         );
+    }
 
-        println!("{}: {}", "Channel".bold(), entry.channel.green());
-        println!("{}: {}", "Theme".bold(), entry.topic.cyan());
-        println!("{}: {}", "Title".bold(), entry.title.bright_white());
+    filtered
+}
 
-        let duration_secs = entry.duration.map_or(0, |d| d.as_secs());
-        let hours = duration_secs / 3600;
-        let minutes = (duration_secs % 3600) / 60;
-        let seconds = duration_secs % 60;
+/// Keep results in order until the next one would exceed the duration budget
+///
+/// Results without a duration don't count against the budget. The achieved
+/// total is reported on stderr.
+fn limit_total_duration(
+    results: Vec<mediathekviewweb::models::Item>,
+    budget: std::time::Duration,
+) -> Vec<mediathekviewweb::models::Item> {
+    let before_count = results.len();
+    let mut total = std::time::Duration::ZERO;
+    let mut kept = Vec::new();
 
-        if hours > 0 {
-            println!(
-                "{}: {}h {}m {}s",
-                "Duration".bold(),
-                hours,
-                minutes,
-                seconds
-            );
-        } else {
-            println!("{}: {}m {}s", "Duration".bold(), minutes, seconds);
+    for entry in results {
+        let length = entry.duration.unwrap_or_default();
+        if total + length > budget {
+            break;
         }
+        total += length;
+        kept.push(entry);
+    }
 
-        if let Some(dt) = DateTime::from_timestamp(entry.timestamp, 0) {
-            println!(
-                "{}: {}",
-                "Date".bold(),
-                dt.format("%Y-%m-%d %H:%M").to_string().yellow()
-            );
-        }
+    tracing::info!(
+        before_count = %before_count,
+        after_count = %kept.len(),
+        total_secs = %total.as_secs(),
+        budget_secs = %budget.as_secs(),
+        "Results limited by total duration"
+    );
+    eprintln!(
+        "⏱️  {} of {} results fit into {}min (total {}min)",
+        kept.len(),
+        before_count,
+        budget.as_secs() / 60,
+        total.as_secs() / 60
+    );
 
-        println!("{}: {}", "Video URL".bold(), entry.url_video.bright_blue());
+    kept
+}
 
-        if let Some(ref description) = entry.description {
-            if !description.is_empty() && description.len() > 10 {
-                let desc = if description.chars().count() > 200 {
-                    let truncated: String = description.chars().take(200).collect();
-                    format!("{truncated}...")
-                } else {
-                    description.clone()
-                };
-                println!("{}: {}", "Description".bold(), desc.bright_black());
-            }
-        }
+/// Number of results fetched for a `--subtract` query
+const SUBTRACT_QUERY_SIZE: usize = 1000;
 
-        println!();
+/// Remove results whose video URL also shows up in the results of a second query
+///
+/// The second query supports the same syntax as the main query, including
+/// duration selectors, and is fetched with up to `SUBTRACT_QUERY_SIZE` results.
+async fn subtract_query_results(
+    client: &Mediathek,
+    results: Vec<mediathekviewweb::models::Item>,
+    subtract_query: &str,
+    exclude_future: bool,
+    timeout: Option<std::time::Duration>,
+) -> Result<Vec<mediathekviewweb::models::Item>> {
+    let (search_terms_only, duration_range) = extract_duration_selectors(subtract_query);
+
+    let mut query_builder = client.query_string(&search_terms_only, false);
+    if let Some(min_duration) = duration_range.min {
+        query_builder = query_builder.duration_min(min_duration);
+    }
+    if let Some(max_duration) = duration_range.max {
+        query_builder = query_builder.duration_max(max_duration);
     }
-}
 
-fn print_csv(results: &[mediathekviewweb::models::Item]) {
-    println!("Channel,Theme,Title,Duration,Date,URL,Description");
+    tracing::info!(subtract_query = %subtract_query, "Fetching results to subtract");
 
-    for entry in results {
+    let subtract_result = send_api_request(
+        query_builder
+            .include_future(!exclude_future)
+            .size(SUBTRACT_QUERY_SIZE)
+            .send(),
+        timeout,
+    )
+    .await?;
+
+    let subtract_urls: std::collections::HashSet<String> = subtract_result
+        .results
+        .into_iter()
+        .map(|entry| entry.url_video)
+        .collect();
+
+    let before_count = results.len();
+    let filtered: Vec<_> = results
+        .into_iter()
+        .filter(|entry| !subtract_urls.contains(&entry.url_video))
+        .collect();
+
+    tracing::info!(
+        before_count = %before_count,
+        after_count = %filtered.len(),
+        subtract_count = %subtract_urls.len(),
+        "Results of subtract query removed"
+    );
+
+    Ok(filtered)
+}
+
+/// Maximum number of concurrent requests made by `--verify-urls`
+const VERIFY_URLS_CONCURRENCY: usize = 8;
+
+/// Limit for each `--verify-urls` request unless `--timeout` sets another
+const VERIFY_URLS_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Check whether a video URL is reachable
+///
+/// Uses a HEAD request; servers that don't allow HEAD are asked for the
+/// first byte with a ranged GET instead.
+async fn is_url_alive(client: &reqwest::Client, url: &str) -> bool {
+    match client.head(url).send().await {
+        Ok(response) if response.status().is_success() => true,
+        Ok(response)
+            if response.status() == reqwest::StatusCode::METHOD_NOT_ALLOWED
+                || response.status() == reqwest::StatusCode::NOT_IMPLEMENTED =>
+        {
+            client
+                .get(url)
+                .header(reqwest::header::RANGE, "bytes=0-0")
+                .send()
+                .await
+                .is_ok_and(|response| response.status().is_success())
+        }
+        Ok(response) => {
+            tracing::debug!(url = %url, status = %response.status(), "Video URL is not reachable");
+            false
+        }
+        Err(e) => {
+            tracing::debug!(url = %url, error = %e, "Video URL request failed");
+            false
+        }
+    }
+}
+
+/// Drop results whose video URL is unreachable, reporting dead links on stderr
+async fn verify_urls(
+    results: Vec<mediathekviewweb::models::Item>,
+    http: &HttpOptions,
+) -> Vec<mediathekviewweb::models::Item> {
+    if results.is_empty() {
+        return results;
+    }
+
+    let client = match http
+        .client_builder(VERIFY_URLS_TIMEOUT)
+        .user_agent(USER_AGENT)
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!(
+                "{}",
+                format!("⚠️  Could not verify URLs, keeping all results: {}", e).yellow()
+            );
+            return results;
+        }
+    };
+
+    eprintln!("🔗 Verifying {} video URLs...", results.len());
+
+    let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(VERIFY_URLS_CONCURRENCY));
+    let mut tasks = tokio::task::JoinSet::new();
+    for (index, entry) in results.iter().enumerate() {
+        let client = client.clone();
+        let semaphore = semaphore.clone();
+        let url = entry.url_video.clone();
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            (index, is_url_alive(&client, &url).await)
+        });
+    }
+
+    let mut alive = vec![false; results.len()];
+    while let Some(joined) = tasks.join_next().await {
+        if let Ok((index, is_alive)) = joined {
+            alive[index] = is_alive;
+        }
+    }
+
+    let mut kept = Vec::with_capacity(results.len());
+    let mut dead_count = 0;
+    for (entry, is_alive) in results.into_iter().zip(alive) {
+        if is_alive {
+            kept.push(entry);
+        } else {
+            dead_count += 1;
+            eprintln!(
+                "{}",
+                format!("   ✗ {} - {}", entry.title, entry.url_video).red()
+            );
+        }
+    }
+
+    tracing::info!(
+        alive = %kept.len(),
+        dead = %dead_count,
+        "Video URL verification completed"
+    );
+    eprintln!(
+        "{}",
+        format!("🔗 {} alive, {} dead (dropped)", kept.len(), dead_count).cyan()
+    );
+
+    kept
+}
+
+/// Variant markers stripped by `normalize_title`, e.g. "(Audiodeskription)" or "- Hörfassung"
+static VARIANT_MARKER_RE: std::sync::LazyLock<Regex> = std::sync::LazyLock::new(|| {
+    Regex::new(
+        r"(?i)\s*(?:[\(\[]\s*(?:mit\s+)?(?:audiodeskription|hörfassung|klare\s+sprache|gebärdensprache|untertitel|originalversion|ov|ut|hd)\s*[\)\]]|[-–|:]\s*(?:audiodeskription|hörfassung|klare\s+sprache|gebärdensprache)\s*$)",
+    )
+    .expect("valid regex")
+});
+
+/// Normalize a title for duplicate detection
+///
+/// Strips the variant markers broadcasters append to otherwise identical
+/// episodes (audio description, clear language, sign language, HD, ...) and
+/// folds case and whitespace. Episode markers like `(S2/E10)` are kept.
+fn normalize_title(title: &str) -> String {
+    VARIANT_MARKER_RE
+        .replace_all(title, "")
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Channel, topic and normalized title, equal for variants of the same episode
+fn duplicate_key(entry: &mediathekviewweb::models::Item) -> (String, String, String) {
+    (
+        entry.channel.to_lowercase(),
+        entry.topic.to_lowercase(),
+        normalize_title(&entry.title),
+    )
+}
+
+/// Drop repeated results, keeping the first (best ranked) occurrence
+///
+/// Results sharing a video URL are always duplicates. With `fuzzy`, results of
+/// the same channel and topic whose titles only differ by a variant marker
+/// (see `normalize_title`) are collapsed as well.
+fn dedup_results(
+    results: Vec<mediathekviewweb::models::Item>,
+    fuzzy: bool,
+) -> Vec<mediathekviewweb::models::Item> {
+    use std::collections::HashSet;
+
+    let before = results.len();
+    let mut seen_urls = HashSet::new();
+    let mut seen_titles = HashSet::new();
+    let deduped: Vec<_> = results
+        .into_iter()
+        .filter(|entry| {
+            if !seen_urls.insert(entry.url_video.clone()) {
+                return false;
+            }
+            !fuzzy || seen_titles.insert(duplicate_key(entry))
+        })
+        .collect();
+
+    tracing::info!(
+        before_count = %before,
+        after_count = %deduped.len(),
+        fuzzy = %fuzzy,
+        "Removed duplicate results"
+    );
+    deduped
+}
+
+/// Print a note to stderr when results contain likely duplicate variants, grouped
+/// by the same key `dedup_results` uses with `fuzzy`
+fn warn_about_duplicates(results: &[mediathekviewweb::models::Item]) {
+    use std::collections::HashMap;
+
+    let mut counts: HashMap<(String, String, String), usize> = HashMap::new();
+    for entry in results {
+        *counts.entry(duplicate_key(entry)).or_insert(0) += 1;
+    }
+
+    let duplicates: usize = counts.values().map(|count| count - 1).sum();
+    if duplicates > 0 {
+        tracing::info!(duplicates = %duplicates, "Likely duplicate variants detected");
+        eprintln!(
+            "{}",
+            format!(
+                "ℹ️  {} likely duplicate variants detected; use --dedup-fuzzy to collapse them",
+                duplicates
+            )
+            .yellow()
+        );
+    }
+}
+
+/// Randomly pick `sample_size` items, keeping their relative (sorted) order
+///
+/// Without an explicit seed a random one is generated and logged so a
+/// particular subset can be reproduced later with `--seed`.
+fn sample_results(
+    results: Vec<mediathekviewweb::models::Item>,
+    sample_size: usize,
+    seed: Option<u64>,
+) -> Vec<mediathekviewweb::models::Item> {
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::collections::HashSet;
+
+    if sample_size >= results.len() {
+        return results;
+    }
+
+    let seed = seed.unwrap_or_else(rand::random);
+    tracing::info!(
+        seed = %seed,
+        sample_size = %sample_size,
+        total_results = %results.len(),
+        "Sampling random subset of results"
+    );
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let picked: HashSet<usize> = rand::seq::index::sample(&mut rng, results.len(), sample_size)
+        .into_iter()
+        .collect();
+
+    results
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| picked.contains(i))
+        .map(|(_, item)| item)
+        .collect()
+}
+
+/// Entries fetched by the wildcard query that `mwb channels` collects channels from
+const CHANNEL_SAMPLE_SIZE: usize = 1000;
+
+/// One line of `mwb channels -f json`
+#[derive(Serialize)]
+struct ChannelCount<'a> {
+    channel: &'a str,
+    count: usize,
+}
+
+async fn list_channels(
+    client: &Mediathek,
+    filter: Option<&str>,
+    columns: usize,
+    column_major: bool,
+    format: ChannelFormat,
+    counts: bool,
+    timeout: Option<std::time::Duration>,
+) -> Result<()> {
+    // Get channels by making a wildcard query and counting each channel's entries
+    let result = send_api_request(
+        client
+            .query_string("", true)
+            .size(CHANNEL_SAMPLE_SIZE)
+            .send(),
+        timeout,
+    )
+    .await?;
+    let mut channel_counts: std::collections::BTreeMap<String, usize> =
+        std::collections::BTreeMap::new();
+    for item in &result.results {
+        *channel_counts.entry(item.channel.clone()).or_default() += 1;
+    }
+    let filter = filter.map(str::to_lowercase);
+    let channels: Vec<(String, usize)> = channel_counts
+        .into_iter()
+        .filter(|(channel, _)| {
+            filter
+                .as_ref()
+                .is_none_or(|filter| channel.to_lowercase().contains(filter))
+        })
+        .collect();
+
+    let mut out = std::io::stdout().lock();
+    match format {
+        ChannelFormat::Json => {
+            let entries: Vec<ChannelCount> = channels
+                .iter()
+                .map(|(channel, count)| ChannelCount {
+                    channel,
+                    count: *count,
+                })
+                .collect();
+            writeln!(out, "{}", serde_json::to_string_pretty(&entries)?)?;
+            return Ok(());
+        }
+        ChannelFormat::Csv => {
+            print_csv_header(&mut out, &["channel", "count"], false)?;
+            for (channel, count) in &channels {
+                print_csv_record(&mut out, &[channel, &count.to_string()])?;
+            }
+            return Ok(());
+        }
+        ChannelFormat::Plain => {
+            for (channel, count) in &channels {
+                if counts {
+                    writeln!(out, "{channel}\t{count}")?;
+                } else {
+                    writeln!(out, "{channel}")?;
+                }
+            }
+            return Ok(());
+        }
+        ChannelFormat::Grid => {}
+    }
+
+    let labels: Vec<String> = channels
+        .iter()
+        .map(|(channel, count)| {
+            if counts {
+                format!("{channel} ({count})")
+            } else {
+                channel.clone()
+            }
+        })
+        .collect();
+
+    if channels.is_empty() && filter.is_some() {
+        println!(
+            "{}",
+            format!(
+                "No channels match '{}'.",
+                filter.as_deref().unwrap_or_default()
+            )
+            .yellow()
+        );
+        return Ok(());
+    }
+
+    println!("{}", "Available Channels:".bold().blue());
+    if counts {
+        println!(
+            "{}",
+            format!("(entries among the latest {CHANNEL_SAMPLE_SIZE} in the index)").dimmed()
+        );
+    }
+    println!();
+
+    // Size columns to the longest channel name like print_theme_count_table does
+    let column_width = labels
+        .iter()
+        .map(|label| label.chars().count())
+        .max()
+        .unwrap_or(0)
+        + 2;
+    // Drop columns that wouldn't fit rather than wrapping rows
+    let columns = columns.min(term_width() / column_width).max(1);
+    let rows = labels.len().div_ceil(columns);
+
+    for row in 0..rows {
+        for column in 0..columns {
+            let index = if column_major {
+                column * rows + row
+            } else {
+                row * columns + column
+            };
+            if let Some(label) = labels.get(index) {
+                print!("{:<width$}", label.green(), width = column_width);
+            }
+        }
+        println!();
+    }
+    println!();
+    println!(
+        "{}: Use {} to filter by channel",
+        "Tip".yellow(),
+        "!CHANNEL".cyan()
+    );
+    println!(
+        "{}: Use {} for duration filtering",
+        "Tip".yellow(),
+        ">90 <120".cyan()
+    );
+
+    Ok(())
+}
+
+/// Order results by season/episode marker, `None` when no title carries one
+///
+/// Episodes without a marker go to the end in broadcast order, and episodes with
+/// the same marker (e.g. a rerun) are ordered by broadcast date. A `None` is
+/// reported to the user, the caller then falls back to the AI.
+fn sort_by_episode(
+    results: &[mediathekviewweb::models::Item],
+) -> Option<Vec<mediathekviewweb::models::Item>> {
+    let mut keyed: Vec<(Option<(u32, u32)>, &mediathekviewweb::models::Item)> = results
+        .iter()
+        .map(|entry| (episode_marker(&entry.title), entry))
+        .collect();
+
+    let unmarked = keyed.iter().filter(|(marker, _)| marker.is_none()).count();
+    if unmarked == keyed.len() {
+        println!(
+            "{}",
+            "No season/episode markers found in the titles, sorting with the AI instead".yellow()
+        );
+        return None;
+    }
+    if unmarked > 0 {
+        println!(
+            "{}",
+            format!(
+                "⚠️  {unmarked} episode(s) have no season/episode marker, placing them at the end by date"
+            )
+            .yellow()
+        );
+    }
+
+    keyed.sort_by_key(|(marker, entry)| (marker.is_none(), *marker, entry.timestamp));
+    tracing::info!(
+        episode_count = %keyed.len(),
+        unmarked = %unmarked,
+        "Sorted episodes by season/episode markers"
+    );
+
+    Some(keyed.into_iter().map(|(_, entry)| entry.clone()).collect())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn create_vlc_playlist_and_launch(
+    results: &[mediathekviewweb::models::Item],
+    query_terms: &[String],
+    quality: &str,
+    minimal: bool,
+    compact: bool,
+    edit: bool,
+    strict_quality: bool,
+    player: &PlayerOptions,
+) -> Result<()> {
+    if results.is_empty() {
+        println!("{}", "No results found to add to playlist.".yellow());
+        return Ok(());
+    }
+
+    let downgraded = log_quality_fallbacks(results, quality);
+    let available: Vec<mediathekviewweb::models::Item>;
+    let results = if strict_quality && downgraded > 0 {
+        available = results
+            .iter()
+            .filter(|entry| quality_url(entry, quality).is_some())
+            .cloned()
+            .collect();
+        if available.is_empty() {
+            println!(
+                "{}",
+                format!(
+                    "No results are available in {} quality, nothing to add to playlist (--strict-quality).",
+                    quality_name(quality)
+                )
+                .yellow()
+            );
+            return Ok(());
+        }
+        &available
+    } else {
+        results
+    };
+
+    // Create playlist filename from query (now XSPF)
+    let playlist_name = playlist_filename(Path::new("."), &query_terms.join(" "), true);
+
+    // Generate XSPF content
+    let xspf_content =
+        generate_xspf_content(results, &query_terms.join(" "), quality, minimal, compact);
+
+    // Write to file
+    let mut file = File::create(&playlist_name)?;
+    writeln!(file, "{xspf_content}")?;
+
+    println!(
+        "{}",
+        format!("Created XSPF playlist: {playlist_name}").green()
+    );
+    println!(
+        "{}",
+        format!("Added {} video(s) to playlist", results.len()).green()
+    );
+    if downgraded > 0 {
+        let message = if strict_quality {
+            format!(
+                "Skipped {} video(s) without a {} version (--strict-quality)",
+                downgraded,
+                quality_name(quality)
+            )
+        } else {
+            format!(
+                "⚠️  {} video(s) have no {} version and use medium quality (--verbose lists them, --strict-quality skips them)",
+                downgraded,
+                quality_name(quality)
+            )
+        };
+        println!("{}", message.yellow());
+    }
+
+    if edit {
+        drop(file);
+        open_in_editor(&playlist_name)?;
+    }
+
+    player.launch(&playlist_name);
+
+    Ok(())
+}
+
+/// Start the player on the first result's video for `--play-first`
+fn play_first(
+    results: &[mediathekviewweb::models::Item],
+    quality: &str,
+    player: &PlayerOptions,
+) -> Result<()> {
+    let Some(entry) = results.first() else {
+        anyhow::bail!("No results found to play (--play-first)");
+    };
+
+    let url = select_video_url(entry, quality);
+    if quality_url(entry, quality).is_none() {
+        println!(
+            "{}",
+            format!(
+                "⚠️  No {} version available, playing medium quality",
+                quality_name(quality)
+            )
+            .yellow()
+        );
+    }
+    println!(
+        "▶️  Playing [{}] {} ({})",
+        entry.channel.bright_cyan(),
+        entry.title.bright_white(),
+        entry.topic.bright_magenta()
+    );
+    tracing::info!(url = %url, title = %entry.title, "Playing first result");
+
+    player.play(url)
+}
+
+/// Open a file in the user's editor and wait until it is closed
+///
+/// Uses `$VISUAL`, then `$EDITOR` (which may carry arguments like `code --wait`),
+/// falling back to Notepad on Windows and vi elsewhere.
+fn open_in_editor(path: &str) -> Result<()> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .ok()
+        .filter(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| {
+            if cfg!(target_os = "windows") {
+                "notepad".to_string()
+            } else {
+                "vi".to_string()
+            }
+        });
+
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+
+    println!("{}", format!("Opening {path} in {program}...").yellow());
+    tracing::info!(editor = %editor, path = %path, "Opening playlist in editor");
+
+    let status = Command::new(program)
+        .args(parts)
+        .arg(path)
+        .status()
+        .map_err(|e| anyhow::anyhow!("Failed to start editor '{}': {}", program, e))?;
+
+    if !status.success() {
+        anyhow::bail!(
+            "Editor '{}' exited with {}, playlist left as is: {}",
+            program,
+            status,
+            path
+        );
+    }
+
+    Ok(())
+}
+
+/// The query-based part of the `-v` playlist name, without extension
+fn vlc_playlist_stem(query: &str) -> String {
+    // Sanitize the query for use as filename
+    let sanitized = query
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' => c,
+            '>' | '<' => 'm', // Convert > to "more", < to "less" indicator
+            _ => '_',         // includes spaces and all other characters
+        })
+        .collect::<String>()
+        .trim_matches('_')
+        .to_string();
+
+    // Limit filename length
+    // (counted in chars, so a cut never lands inside a multibyte character)
+    let max_len = 50;
+    let truncated = if sanitized.chars().count() > max_len {
+        let partial = sanitized.chars().take(max_len).collect::<String>();
+        format!("{}...", partial.trim_end_matches('_'))
+    } else if sanitized.is_empty() {
+        // Queries without a single letter or digit still get a usable name
+        "playlist".to_string()
+    } else {
+        sanitized
+    };
+
+    format!("mwb_{truncated}")
+}
+
+/// The playlist name `-v` (`vlc`) or `-x` writes for a query in `dir`
+///
+/// The first free one of `stem.xspf`, `stem_2.xspf`, ... It only depends on the
+/// query and the files already in `dir`, so `--print-filename` prints the name
+/// a following run really writes.
+fn playlist_filename(dir: &Path, query: &str, vlc: bool) -> String {
+    let stem = if vlc {
+        vlc_playlist_stem(query)
+    } else {
+        xspf_stem(query)
+    };
+    std::iter::once(format!("{stem}.xspf"))
+        .chain((2..).map(|n| format!("{stem}_{n}.xspf")))
+        .find(|name| !dir.join(name).exists())
+        .expect("an unbounded range always has a free name")
+}
+
+async fn process_with_ai(
+    results: &[mediathekviewweb::models::Item],
+    search_info: &[String],
+    ai_options: AIOptions,
+) -> Result<()> {
+    if results.is_empty() {
+        println!("{}", "No results found to process with AI.".yellow());
+        return Ok(());
+    }
+
+    // Load environment variables from .env file if it exists
+    dotenvy::dotenv().ok();
+
+    println!("{}", "🚀 Initializing Gemini AI processor...".yellow());
+
+    let processor = match AIProcessor::new_with_verbose(search_info, ai_options).await {
+        Ok(processor) => processor,
+        Err(e) => {
+            println!(
+                "{}",
+                format!("❌ Failed to initialize AI processor: {}", e).red()
+            );
+            println!(
+                "{}",
+                "💡 Make sure you have set GOOGLE_API_KEY in your environment or .env file"
+                    .yellow()
+            );
+            println!(
+                "{}",
+                "   You can get an API key from: https://aistudio.google.com/app/apikey".cyan()
+            );
+            return Ok(());
+        }
+    };
+
+    match processor.process_episodes(results).await {
+        Ok(response) => {
+            println!("\n{}", "✅ AI Processing Results:".green().bold());
+            println!("{}", "=".repeat(50).green());
+            println!("{}", response);
+            println!("{}", "=".repeat(50).green());
+
+            // Optionally save the results to a file
+            let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
+            let filename = format!("ai_sorted_episodes_{}.txt", timestamp);
+
+            if let Ok(mut file) = File::create(&filename) {
+                writeln!(
+                    file,
+                    "AI Sorted Episodes - Generated on {}",
+                    chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC")
+                )?;
+                writeln!(file, "{}", "=".repeat(70))?;
+                writeln!(file, "{}", response)?;
+                println!("\n{}", format!("📄 Results saved to: {}", filename).cyan());
+            }
+        }
+        Err(e) => {
+            let error_msg = e.to_string().to_lowercase();
+
+            if error_msg.contains("401")
+                || error_msg.contains("unauthorized")
+                || error_msg.contains("api key")
+            {
+                println!("{}", "🔑 API Key Issue Detected!".yellow().bold());
+                println!();
+                println!("{}", "❌ There's a problem with your Google API key.".red());
+                println!();
+                println!("{}", "💡 To fix this:".cyan().bold());
+                println!(
+                    "{}",
+                    "   1. Visit: https://aistudio.google.com/app/u/5/apikey".cyan()
+                );
+                println!("{}", "   2. Generate a new API key if needed".cyan());
+                println!(
+                    "{}",
+                    "   3. Copy the key to your .env file as GOOGLE_API_KEY=your_key_here".cyan()
+                );
+                println!();
+                println!("{}", "🌐 Opening API key page in your browser...".green());
+
+                // Try to open the API key page in browser
+                let url = "https://aistudio.google.com/app/u/5/apikey";
+                let _ = open_browser_url(url);
+            } else if error_msg.contains("429")
+                || error_msg.contains("quota")
+                || error_msg.contains("rate limit")
+            {
+                println!("{}", "⏱️  API Quota/Rate Limit Exceeded!".yellow().bold());
+                println!();
+                println!("{}", "❌ You've exceeded the API quota limits.".red());
+                println!();
+                println!("{}", "💡 Solutions:".cyan().bold());
+                println!("{}", "   1. Wait a few minutes and try again".cyan());
+                println!(
+                    "{}",
+                    "   2. Check your quota limits at the API console".cyan()
+                );
+                println!(
+                    "{}",
+                    "   3. Consider upgrading to a paid plan for higher limits".cyan()
+                );
+                println!();
+                println!(
+                    "{}",
+                    "🌐 Opening Google AI Studio to check your usage...".green()
+                );
+
+                let url = "https://aistudio.google.com/app/u/5/apikey";
+                let _ = open_browser_url(url);
+            } else {
+                println!("{}", format!("❌ AI processing failed: {}", e).red());
+                println!("{}", "💡 The AI might need more specific episode information or the search tools might be having issues".yellow());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Open URL in the default browser
+fn open_browser_url(url: &str) -> Result<()> {
+    #[cfg(target_os = "windows")]
+    {
+        Command::new("cmd").args(["/C", "start", url]).spawn()?;
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        Command::new("open").arg(url).spawn()?;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        Command::new("xdg-open").arg(url).spawn()?;
+    }
+
+    Ok(())
+}
+
+/// Compare two items by a sort field (timestamp, duration, channel, title, topic) and order (asc, desc)
+///
+/// Titles and topics compare naturally, so "Folge 2" comes before "Folge 10".
+/// Unknown fields sort by timestamp, matching the API's default. Ties are
+/// broken by timestamp, then video URL, so the order is reproducible between
+/// runs whatever order the API returned equal items in.
+fn compare_items(
+    a: &mediathekviewweb::models::Item,
+    b: &mediathekviewweb::models::Item,
+    sort_by: &str,
+    sort_order: &str,
+) -> std::cmp::Ordering {
+    let ordering = match sort_by {
+        "duration" => {
+            let duration_a = a.duration.map(|d| d.as_secs()).unwrap_or(0);
+            let duration_b = b.duration.map(|d| d.as_secs()).unwrap_or(0);
+            duration_a.cmp(&duration_b)
+        }
+        "channel" => a.channel.cmp(&b.channel),
+        "title" => natural_cmp(&a.title, &b.title),
+        "topic" => natural_cmp(&a.topic, &b.topic).then_with(|| natural_cmp(&a.title, &b.title)),
+        _ => a.timestamp.cmp(&b.timestamp),
+    }
+    .then_with(|| a.timestamp.cmp(&b.timestamp))
+    .then_with(|| a.url_video.cmp(&b.url_video));
+
+    match sort_order {
+        "asc" => ordering,
+        _ => ordering.reverse(),
+    }
+}
+
+/// Compare strings with embedded numbers by value ("Folge 2" < "Folge 10")
+///
+/// Text compares case-insensitively, digit runs by their numeric value (without
+/// overflowing on long runs). Strings equal under these rules fall back to a
+/// plain comparison so the order is total.
+fn natural_cmp(a: &str, b: &str) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+
+    /// Take the run of ASCII digits at the front, without leading zeros
+    fn take_number(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+        let mut number = String::new();
+        while let Some(c) = chars.next_if(char::is_ascii_digit) {
+            if !(number.is_empty() && c == '0') {
+                number.push(c);
+            }
+        }
+        number
+    }
+
+    let mut a_chars = a.chars().peekable();
+    let mut b_chars = b.chars().peekable();
+    loop {
+        let ordering = match (a_chars.peek(), b_chars.peek()) {
+            (None, None) => return a.cmp(b),
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let number_a = take_number(&mut a_chars);
+                let number_b = take_number(&mut b_chars);
+                number_a
+                    .len()
+                    .cmp(&number_b.len())
+                    .then_with(|| number_a.cmp(&number_b))
+            }
+            (Some(&x), Some(&y)) => {
+                a_chars.next();
+                b_chars.next();
+                x.to_lowercase().cmp(y.to_lowercase())
+            }
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+/// Put results of the same channel next to each other, channels in alphabetical order
+///
+/// The sort is stable: without `within_group_sort` each channel keeps the order of
+/// the global sort, otherwise its items are ordered by that field and `sort_order`.
+fn group_by_channel(
+    mut results: Vec<mediathekviewweb::models::Item>,
+    within_group_sort: Option<&str>,
+    sort_order: &str,
+) -> Vec<mediathekviewweb::models::Item> {
+    results.sort_by(|a, b| {
+        a.channel
+            .cmp(&b.channel)
+            .then_with(|| match within_group_sort {
+                Some(field) => compare_items(a, b, field, sort_order),
+                None => std::cmp::Ordering::Equal,
+            })
+    });
+    results
+}
+
+fn print_table(
+    out: &mut impl Write,
+    results: &[mediathekviewweb::models::Item],
+    query_info: &mediathekviewweb::models::QueryInfo,
+    group_by_channel: bool,
+) -> Result<()> {
+    writeln!(out, "{}", "Search Results".bold().blue())?;
+    writeln!(
+        out,
+        "Total results: {}",
+        query_info.total_results.to_string().green()
+    )?;
+    writeln!(
+        out,
+        "Showing: {}",
+        query_info.result_count.to_string().green()
+    )?;
+    let search_time = query_info.search_engine_time.as_millis();
+    writeln!(
+        out,
+        "Search time: {}ms",
+        format!("{search_time:.2}").yellow()
+    )?;
+    writeln!(out)?;
+
+    if results.is_empty() {
+        writeln!(out, "{}", "No results found.".yellow())?;
+        return Ok(());
+    }
+
+    for (i, entry) in results.iter().enumerate() {
+        if group_by_channel && (i == 0 || results[i - 1].channel != entry.channel) {
+            let group_size = results
+                .iter()
+                .filter(|other| other.channel == entry.channel)
+                .count();
+            writeln!(
+                out,
+                "{} {}",
+                format!("══ {} ({group_size})", entry.channel)
+                    .green()
+                    .bold(),
+                "═".repeat(40).green()
+            )?;
+            writeln!(out)?;
+        }
+
+        let entry_num = i + 1;
+        writeln!(
+            out,
+            "{} {}",
+            format!("{entry_num}.").blue().bold(),
+            "─".repeat(60).blue()
+        )?;
+
+        writeln!(out, "{}: {}", "Channel".bold(), entry.channel.green())?;
+        writeln!(out, "{}: {}", "Theme".bold(), entry.topic.cyan())?;
+        writeln!(out, "{}: {}", "Title".bold(), entry.title.bright_white())?;
+
+        let duration_secs = entry.duration.map_or(0, |d| d.as_secs());
+        let hours = duration_secs / 3600;
+        let minutes = (duration_secs % 3600) / 60;
+        let seconds = duration_secs % 60;
+
+        if hours > 0 {
+            writeln!(
+                out,
+                "{}: {}h {}m {}s",
+                "Duration".bold(),
+                hours,
+                minutes,
+                seconds
+            )?;
+        } else {
+            writeln!(out, "{}: {}m {}s", "Duration".bold(), minutes, seconds)?;
+        }
+
+        if let Some(dt) = DateTime::from_timestamp(entry.timestamp, 0) {
+            writeln!(
+                out,
+                "{}: {}",
+                "Date".bold(),
+                dt.format("%Y-%m-%d %H:%M").to_string().yellow()
+            )?;
+        }
+
+        writeln!(
+            out,
+            "{}: {}",
+            "Video URL".bold(),
+            entry.url_video.bright_blue()
+        )?;
+
+        if let Some(ref description) = entry.description {
+            if !description.is_empty() && description.len() > 10 {
+                let desc = if description.chars().count() > 200 {
+                    let truncated: String = description.chars().take(200).collect();
+                    format!("{truncated}...")
+                } else {
+                    description.clone()
+                };
+                writeln!(out, "{}: {}", "Description".bold(), desc.bright_black())?;
+            }
+        }
+
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
+
+/// Quote a CSV field per RFC 4180
+///
+/// Quotes are doubled and line breaks stay inside the quoted field, normalized
+/// to `\n` so they can't be mistaken for the `\r\n` record separator.
+fn csv_field(text: &str) -> String {
+    let text = text.replace("\r\n", "\n").replace('\r', "\n");
+    format!("\"{}\"", text.replace('"', "\"\""))
+}
+
+/// Print one CSV record, terminated by CRLF as RFC 4180 requires
+fn print_csv_record(out: &mut impl Write, fields: &[&str]) -> Result<()> {
+    let record = fields
+        .iter()
+        .map(|field| csv_field(field))
+        .collect::<Vec<_>>()
+        .join(",");
+    write!(out, "{record}\r\n")?;
+
+    Ok(())
+}
+
+/// Print the CSV header, preceded by a UTF-8 BOM if requested so Excel detects the encoding
+fn print_csv_header(out: &mut impl Write, columns: &[&str], bom: bool) -> Result<()> {
+    if bom {
+        write!(out, "\u{feff}")?;
+    }
+    write!(out, "{}\r\n", columns.join(","))?;
+
+    Ok(())
+}
+
+fn print_csv(
+    out: &mut impl Write,
+    results: &[mediathekviewweb::models::Item],
+    bom: bool,
+) -> Result<()> {
+    print_csv_header(
+        out,
+        &[
+            "Channel",
+            "Theme",
+            "Title",
+            "Duration",
+            "Date",
+            "URL",
+            "Description",
+        ],
+        bom,
+    )?;
+
+    for entry in results {
         let duration = entry
             .duration
             .map_or("0".to_string(), |d| d.as_secs().to_string());
@@ -1076,83 +3793,388 @@ fn print_csv(results: &[mediathekviewweb::models::Item]) {
             .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
             .unwrap_or_default();
 
-        println!(
-            "\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\"",
-            entry.channel.replace('"', "\"\""),
-            entry.topic.replace('"', "\"\""),
-            entry.title.replace('"', "\"\""),
-            duration,
-            date,
-            entry.url_video,
-            entry
-                .description
-                .as_deref()
-                .unwrap_or("")
-                .replace('"', "\"\"")
-        );
+        print_csv_record(
+            out,
+            &[
+                &entry.channel,
+                &entry.topic,
+                &entry.title,
+                &duration,
+                &date,
+                &entry.url_video,
+                entry.description.as_deref().unwrap_or(""),
+            ],
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Turn text into a portable filename component
+///
+/// Letters (including umlauts) and digits are kept, runs of anything else
+/// become a single `_`.
+fn sanitize_filename_component(text: &str) -> String {
+    let mut sanitized = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c.is_alphanumeric() {
+            sanitized.push(c);
+        } else if !sanitized.ends_with('_') {
+            sanitized.push('_');
+        }
+    }
+    sanitized.trim_matches('_').to_string()
+}
+
+/// File extension of a video URL's path, `mp4` when it has none that looks valid
+fn video_extension(url: &str) -> String {
+    url::Url::parse(url)
+        .ok()
+        .and_then(|url| {
+            url.path()
+                .rsplit_once('.')
+                .map(|(_, ext)| ext.to_lowercase())
+        })
+        .filter(|ext| {
+            !ext.is_empty() && ext.len() <= 4 && ext.chars().all(|c| c.is_ascii_alphanumeric())
+        })
+        .unwrap_or_else(|| "mp4".to_string())
+}
+
+/// Suggested download filename for a result: `Topic_Title.ext`, extension from the video URL
+fn media_filename(entry: &mediathekviewweb::models::Item) -> String {
+    let extension = video_extension(&entry.url_video);
+
+    let stem = sanitize_filename_component(&format!("{} {}", entry.topic, entry.title));
+    if stem.is_empty() {
+        format!("mwb_video.{extension}")
+    } else {
+        format!("{stem}.{extension}")
+    }
+}
+
+/// Print a CSV whose columns map onto common ID3/metadata fields for batch taggers
+fn print_id3_csv(
+    out: &mut impl Write,
+    results: &[mediathekviewweb::models::Item],
+    bom: bool,
+) -> Result<()> {
+    print_csv_header(
+        out,
+        &["Artist", "Album", "Title", "Year", "Comment", "Filename"],
+        bom,
+    )?;
+
+    for entry in results {
+        let year = DateTime::from_timestamp(entry.timestamp, 0)
+            .map(|dt| dt.format("%Y").to_string())
+            .unwrap_or_default();
+
+        print_csv_record(
+            out,
+            &[
+                &entry.channel,
+                &entry.topic,
+                &entry.title,
+                &year,
+                entry.description.as_deref().unwrap_or(""),
+                &media_filename(entry),
+            ],
+        )?;
+    }
+
+    Ok(())
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonItem {
+    channel: String,
+    topic: String,
+    title: String,
+    timestamp: i64,
+    date_human: String,
+    duration_seconds: Option<u64>,
+    duration_human: Option<String>,
+    url_video: String,
+    url_video_low: Option<String>,
+    url_video_hd: Option<String>,
+    url_subtitle: Option<String>,
+    description: Option<String>,
+}
+
+impl From<&mediathekviewweb::models::Item> for JsonItem {
+    fn from(entry: &mediathekviewweb::models::Item) -> Self {
+        let date_human = DateTime::from_timestamp(entry.timestamp, 0)
+            .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default();
+
+        let duration_seconds = entry.duration.map(|d| d.as_secs());
+        let duration_human = entry.duration.map(|d| {
+            let total_secs = d.as_secs();
+            let hours = total_secs / 3600;
+            let minutes = (total_secs % 3600) / 60;
+            let seconds = total_secs % 60;
+
+            if hours > 0 {
+                format!("{}h {}m {}s", hours, minutes, seconds)
+            } else if minutes > 0 {
+                format!("{}m {}s", minutes, seconds)
+            } else {
+                format!("{}s", seconds)
+            }
+        });
+
+        JsonItem {
+            channel: entry.channel.clone(),
+            topic: entry.topic.clone(),
+            title: entry.title.clone(),
+            timestamp: entry.timestamp,
+            date_human,
+            duration_seconds,
+            duration_human,
+            url_video: entry.url_video.clone(),
+            url_video_low: entry.url_video_low.clone(),
+            url_video_hd: entry.url_video_hd.clone(),
+            url_subtitle: subtitle_url(entry).map(str::to_string),
+            description: entry.description.clone(),
+        }
+    }
+}
+
+/// `JsonItem` extended with Dublin Core terms for ingestion by archive systems
+#[derive(Serialize)]
+struct JsonItemWithMeta {
+    #[serde(flatten)]
+    item: JsonItem,
+    #[serde(rename = "dc:creator")]
+    dc_creator: String,
+    #[serde(rename = "dc:date")]
+    dc_date: String,
+    #[serde(rename = "dc:title")]
+    dc_title: String,
+    #[serde(rename = "dc:description")]
+    dc_description: Option<String>,
+    #[serde(rename = "dc:identifier")]
+    dc_identifier: String,
+}
+
+impl From<&mediathekviewweb::models::Item> for JsonItemWithMeta {
+    fn from(entry: &mediathekviewweb::models::Item) -> Self {
+        // W3C-DTF (ISO 8601) as recommended for dc:date
+        let dc_date = DateTime::from_timestamp(entry.timestamp, 0)
+            .map(|dt| dt.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+            .unwrap_or_default();
+
+        JsonItemWithMeta {
+            item: JsonItem::from(entry),
+            dc_creator: entry.channel.clone(),
+            dc_date,
+            dc_title: entry.title.clone(),
+            dc_description: entry.description.clone(),
+            dc_identifier: entry.url_video.clone(),
+        }
+    }
+}
+
+/// API statistics of one search as reported in its `QueryInfo`
+#[derive(Serialize)]
+struct TermStats {
+    query: String,
+    search_engine_time_ms: u64,
+    result_count: usize,
+    total_results: u64,
+}
+
+impl TermStats {
+    fn new(query: &str, query_info: &mediathekviewweb::models::QueryInfo) -> Self {
+        TermStats {
+            query: query.to_string(),
+            search_engine_time_ms: query_info.search_engine_time.as_millis() as u64,
+            result_count: query_info.result_count,
+            total_results: query_info.total_results,
+        }
+    }
+}
+
+/// API statistics recorded in the `--with-meta` envelope, for tracking latency over time
+#[derive(Serialize)]
+struct SearchStats {
+    /// Search engine time, summed over the searches of a multi-search
+    search_engine_time_ms: u64,
+    /// Results the API returned, before client-side filtering
+    result_count: usize,
+    total_results: u64,
+    /// Per-term statistics, only for multi-search
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    terms: Vec<TermStats>,
+}
+
+impl SearchStats {
+    fn single(query_info: &mediathekviewweb::models::QueryInfo) -> Self {
+        SearchStats {
+            search_engine_time_ms: query_info.search_engine_time.as_millis() as u64,
+            result_count: query_info.result_count,
+            total_results: query_info.total_results,
+            terms: Vec::new(),
+        }
+    }
+
+    fn multi(terms: Vec<TermStats>) -> Self {
+        SearchStats {
+            search_engine_time_ms: terms.iter().map(|term| term.search_engine_time_ms).sum(),
+            result_count: terms.iter().map(|term| term.result_count).sum(),
+            total_results: terms.iter().map(|term| term.total_results).sum(),
+            terms,
+        }
     }
 }
 
-#[derive(Serialize, Deserialize)]
-struct JsonItem {
-    channel: String,
-    topic: String,
-    title: String,
-    timestamp: i64,
-    date_human: String,
-    duration_seconds: Option<u64>,
-    duration_human: Option<String>,
-    url_video: String,
-    url_video_low: Option<String>,
-    url_video_hd: Option<String>,
-    description: Option<String>,
+/// The `meta` object: the saved query with the API statistics alongside
+///
+/// `--from-file` only reads the saved query fields and ignores the statistics.
+#[derive(Serialize)]
+struct JsonMeta<'a> {
+    #[serde(flatten)]
+    query: &'a SavedQuery,
+    #[serde(flatten)]
+    stats: &'a SearchStats,
 }
 
-fn print_json(results: &[mediathekviewweb::models::Item]) -> Result<()> {
-    let json_items: Vec<JsonItem> = results
-        .iter()
-        .map(|entry| {
-            let date_human = DateTime::from_timestamp(entry.timestamp, 0)
-                .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
-                .unwrap_or_default();
+/// `--with-meta` JSON output: the recorded search plus Dublin Core enriched results
+#[derive(Serialize)]
+struct JsonWithMeta<'a> {
+    meta: JsonMeta<'a>,
+    results: Vec<JsonItemWithMeta>,
+}
 
-            let duration_seconds = entry.duration.map(|d| d.as_secs());
-            let duration_human = entry.duration.map(|d| {
-                let total_secs = d.as_secs();
-                let hours = total_secs / 3600;
-                let minutes = (total_secs % 3600) / 60;
-                let seconds = total_secs % 60;
-
-                if hours > 0 {
-                    format!("{}h {}m {}s", hours, minutes, seconds)
-                } else if minutes > 0 {
-                    format!("{}m {}s", minutes, seconds)
-                } else {
-                    format!("{}s", seconds)
-                }
-            });
+/// The results as a JSON array, each `JsonItem` converted while it's written
+struct JsonItems<'a>(&'a [mediathekviewweb::models::Item]);
 
-            JsonItem {
-                channel: entry.channel.clone(),
-                topic: entry.topic.clone(),
-                title: entry.title.clone(),
-                timestamp: entry.timestamp,
-                date_human,
-                duration_seconds,
-                duration_human,
-                url_video: entry.url_video.clone(),
-                url_video_low: entry.url_video_low.clone(),
-                url_video_hd: entry.url_video_hd.clone(),
-                description: entry.description.clone(),
-            }
-        })
-        .collect();
+impl Serialize for JsonItems<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().map(JsonItem::from))
+    }
+}
+
+/// Print the results as a JSON array, pretty-printed or on one line with `compact`
+///
+/// The JSON is serialized straight into `out` rather than built as a string first.
+fn print_json(
+    out: &mut impl Write,
+    results: &[mediathekviewweb::models::Item],
+    meta: Option<(&SavedQuery, &SearchStats)>,
+    compact: bool,
+) -> Result<()> {
+    if let Some((query, stats)) = meta {
+        let json = JsonWithMeta {
+            meta: JsonMeta { query, stats },
+            results: results.iter().map(Into::into).collect(),
+        };
+        write_json(out, &json, compact)?;
+    } else {
+        write_json(out, &JsonItems(results), compact)?;
+    }
+
+    writeln!(out)?;
+    Ok(())
+}
+
+/// Serialize a value into `out`, pretty-printed unless `compact`
+fn write_json(out: &mut impl Write, value: &impl Serialize, compact: bool) -> Result<()> {
+    if compact {
+        serde_json::to_writer(&mut *out, value)?;
+    } else {
+        serde_json::to_writer_pretty(&mut *out, value)?;
+    }
+    Ok(())
+}
+
+/// Print one compact `JsonItem` per line, serialized as it's written so nothing is buffered
+fn print_ndjson(out: &mut impl Write, results: &[mediathekviewweb::models::Item]) -> Result<()> {
+    for entry in results {
+        serde_json::to_writer(&mut *out, &JsonItem::from(entry))?;
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+/// Print the results as a YAML sequence with the same fields as `print_json`
+fn print_yaml(
+    out: &mut impl Write,
+    results: &[mediathekviewweb::models::Item],
+    meta: Option<(&SavedQuery, &SearchStats)>,
+) -> Result<()> {
+    let output = if let Some((query, stats)) = meta {
+        let yaml = JsonWithMeta {
+            meta: JsonMeta { query, stats },
+            results: results.iter().map(Into::into).collect(),
+        };
+        serde_norway::to_string(&yaml)?
+    } else {
+        let yaml_items: Vec<JsonItem> = results.iter().map(Into::into).collect();
+        serde_norway::to_string(&yaml_items)?
+    };
+
+    // serde_norway already ends the document with a newline
+    write!(out, "{}", output)?;
+    Ok(())
+}
+
+/// Compact `{url, title}` line emitted per result by the `jsonl-urls` format
+#[derive(Serialize)]
+struct UrlLine<'a> {
+    url: &'a str,
+    title: &'a str,
+}
+
+/// Print one compact `{"url": ..., "title": ...}` object per line, e.g. for downloaders
+/// that want a filename hint alongside the URL
+fn print_jsonl_urls(
+    out: &mut impl Write,
+    results: &[mediathekviewweb::models::Item],
+    quality: &str,
+) -> Result<()> {
+    for entry in results {
+        let line = UrlLine {
+            url: select_video_url(entry, quality),
+            title: &entry.title,
+        };
+        writeln!(out, "{}", serde_json::to_string(&line)?)?;
+    }
+    Ok(())
+}
+
+/// Print one line per result in the user's `--template` layout
+fn print_template(
+    out: &mut impl Write,
+    results: &[mediathekviewweb::models::Item],
+    template: Option<&OutputTemplate>,
+) -> Result<()> {
+    let template = template.ok_or_else(|| anyhow::anyhow!("-f template needs --template"))?;
+    for entry in results {
+        writeln!(out, "{}", template.render(entry))?;
+    }
+    Ok(())
+}
+
+/// Print only numbered titles, the most compact human-readable listing
+fn print_brief(out: &mut impl Write, results: &[mediathekviewweb::models::Item]) -> Result<()> {
+    let width = results.len().to_string().len();
+
+    for (index, entry) in results.iter().enumerate() {
+        // Format: N. Title
+        let number = format!("{:>width$}.", index + 1);
+        writeln!(out, "{} {}", number.dimmed(), entry.title)?;
+    }
 
-    println!("{}", serde_json::to_string_pretty(&json_items)?);
     Ok(())
 }
 
-fn print_oneline(results: &[mediathekviewweb::models::Item]) {
+fn print_oneline(
+    out: &mut impl Write,
+    results: &[mediathekviewweb::models::Item],
+    max_title_length: Option<usize>,
+) -> Result<()> {
     for entry in results {
         let date = DateTime::from_timestamp(entry.timestamp, 0)
             .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
@@ -1162,11 +4184,17 @@ fn print_oneline(results: &[mediathekviewweb::models::Item]) {
             .duration
             .map_or("".to_string(), |d| format!("{}min", d.as_secs() / 60));
 
+        let title = match max_title_length {
+            Some(max) => truncate_with_ellipsis(&entry.title, max),
+            None => entry.title.clone(),
+        };
+
         // Format: [Channel] Title (Date) [Duration] - URL
-        println!(
+        writeln!(
+            out,
             "[{}] {} ({}) {} - {}",
             entry.channel.bright_cyan(),
-            entry.title.bright_white(),
+            title.bright_white(),
             date.yellow(),
             if duration.is_empty() {
                 "".to_string()
@@ -1174,94 +4202,566 @@ fn print_oneline(results: &[mediathekviewweb::models::Item]) {
                 format!("[{}]", duration.green())
             },
             entry.url_video.bright_blue()
-        );
+        )?;
     }
+
+    Ok(())
 }
 
-fn print_oneline_theme(results: &[mediathekviewweb::models::Item]) {
+fn print_oneline_theme(
+    out: &mut impl Write,
+    results: &[mediathekviewweb::models::Item],
+    max_title_length: Option<usize>,
+) -> Result<()> {
     for entry in results {
         let date = DateTime::from_timestamp(entry.timestamp, 0)
             .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
             .unwrap_or_default();
 
-        let duration = entry
-            .duration
-            .map_or("".to_string(), |d| format!("{}min", d.as_secs() / 60));
+        let duration = entry
+            .duration
+            .map_or("".to_string(), |d| format!("{}min", d.as_secs() / 60));
+
+        let title = match max_title_length {
+            Some(max) => truncate_with_ellipsis(&entry.title, max),
+            None => entry.title.clone(),
+        };
+
+        // Format: [Channel] Title (Date) [Duration] - Theme
+        writeln!(
+            out,
+            "[{}] {} ({}) {} - {}",
+            entry.channel.bright_cyan(),
+            title.bright_white(),
+            date.yellow(),
+            if duration.is_empty() {
+                "".to_string()
+            } else {
+                format!("[{}]", duration.green())
+            },
+            entry.topic.bright_magenta()
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Print upcoming broadcasts as a TV guide, e.g.
+/// `Mon 03.02 20:15  ARD  Tatort: Titel (90min)`
+fn print_schedule(out: &mut impl Write, results: &[mediathekviewweb::models::Item]) -> Result<()> {
+    let now = chrono::Utc::now().timestamp();
+
+    let mut upcoming: Vec<&mediathekviewweb::models::Item> = results
+        .iter()
+        .filter(|entry| entry.timestamp > now)
+        .collect();
+    upcoming.sort_by_key(|entry| entry.timestamp);
+
+    if upcoming.is_empty() {
+        writeln!(out, "{}", "No upcoming broadcasts found.".yellow())?;
+        return Ok(());
+    }
+
+    let channel_width = upcoming
+        .iter()
+        .map(|entry| entry.channel.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    for entry in upcoming {
+        let when = DateTime::from_timestamp(entry.timestamp, 0)
+            .map(|dt| dt.format("%a %d.%m %H:%M").to_string())
+            .unwrap_or_default();
+
+        let duration = entry
+            .duration
+            .map_or("".to_string(), |d| format!(" ({}min)", d.as_secs() / 60));
+
+        writeln!(
+            out,
+            "{}  {:<width$}  {}: {}{}",
+            when.yellow(),
+            entry.channel.bright_cyan(),
+            entry.topic.bright_magenta(),
+            entry.title.bright_white(),
+            duration.green(),
+            width = channel_width
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Format seconds as a WebVTT timestamp (`HH:MM:SS.mmm`)
+fn format_vtt_timestamp(total_secs: u64) -> String {
+    format!(
+        "{:02}:{:02}:{:02}.000",
+        total_secs / 3600,
+        (total_secs % 3600) / 60,
+        total_secs % 60
+    )
+}
+
+/// Print a WebVTT chapter index for playing the results back to back
+///
+/// Each cue starts where the previous item ends, so players that accept a
+/// chapter file over a concatenated stream can jump between episodes.
+fn print_vtt_index(out: &mut impl Write, results: &[mediathekviewweb::models::Item]) -> Result<()> {
+    writeln!(out, "WEBVTT")?;
+
+    let mut offset = 0u64;
+    let mut missing_duration = 0;
+
+    for (index, entry) in results.iter().enumerate() {
+        let length = match entry.duration {
+            Some(d) => d.as_secs(),
+            None => {
+                missing_duration += 1;
+                0
+            }
+        };
+
+        writeln!(out)?;
+        writeln!(out, "{}", index + 1)?;
+        writeln!(
+            out,
+            "{} --> {}",
+            format_vtt_timestamp(offset),
+            format_vtt_timestamp(offset + length)
+        )?;
+        writeln!(out, "{}", entry.title.replace("-->", "->"))?;
+
+        offset += length;
+    }
+
+    if missing_duration > 0 {
+        eprintln!(
+            "{}",
+            format!(
+                "⚠️  {} item(s) without duration got zero-length chapters, later offsets may be off",
+                missing_duration
+            )
+            .yellow()
+        );
+    }
+
+    Ok(())
+}
+
+/// Narrowest a column is clamped to, so tables stay usable in tiny terminals
+const MIN_CLAMPED_COLUMN_WIDTH: usize = 12;
+
+/// Width assumed when stdout isn't a terminal and `COLUMNS` isn't set
+const DEFAULT_TERM_WIDTH: usize = 80;
+
+/// Width available for output, used by everything that sizes itself to the terminal
+///
+/// Falls back to `$COLUMNS` and then to `DEFAULT_TERM_WIDTH` when the size can't
+/// be determined (redirected output, minimal environments), and never returns 0.
+fn term_width() -> usize {
+    terminal_size::terminal_size()
+        .map(|(terminal_size::Width(width), _)| width as usize)
+        .or_else(|| {
+            std::env::var("COLUMNS")
+                .ok()
+                .and_then(|columns| columns.trim().parse().ok())
+        })
+        .filter(|&width| width > 0)
+        .unwrap_or(DEFAULT_TERM_WIDTH)
+}
+
+/// Shorten text to at most `max_chars` characters, marking cuts with an ellipsis
+fn truncate_with_ellipsis(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+
+    let mut truncated: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
+
+fn print_theme_count_table(
+    out: &mut impl Write,
+    results: &[mediathekviewweb::models::Item],
+    percent: bool,
+) -> Result<()> {
+    use std::collections::HashMap;
+
+    // Count themes
+    let mut theme_counts: HashMap<String, u32> = HashMap::new();
+    for entry in results {
+        *theme_counts.entry(entry.topic.clone()).or_insert(0) += 1;
+    }
+
+    // Convert to vector and sort by count (descending)
+    let mut sorted_themes: Vec<(String, u32)> = theme_counts.into_iter().collect();
+    sorted_themes.sort_by(|a, b| b.1.cmp(&a.1));
+
+    if sorted_themes.is_empty() {
+        writeln!(out, "No themes found.")?;
+        return Ok(());
+    }
+
+    // Calculate optimal column width based on longest theme name
+    let max_theme_length = sorted_themes
+        .iter()
+        .map(|(theme, _)| theme.chars().count())
+        .max()
+        .unwrap_or(10);
+    let mut theme_width = std::cmp::max(max_theme_length + 2, 25); // Minimum 25 chars for "Theme" header
+    let columns_width = if percent { 18 } else { 10 }; // count (+ share) columns and spacing
+
+    // Don't overflow narrow terminals, long theme names get truncated instead
+    theme_width = theme_width.min(
+        term_width()
+            .saturating_sub(columns_width)
+            .max(MIN_CLAMPED_COLUMN_WIDTH),
+    );
+    let total_width = theme_width + columns_width;
+
+    // Print header
+    writeln!(out, "{}", "Theme Count Report".bold().underline())?;
+    writeln!(out, "{}", "─".repeat(total_width))?;
+    if percent {
+        writeln!(
+            out,
+            "{:<width$} {:>6} {:>7}",
+            "Theme".bold(),
+            "Count".bold(),
+            "Share".bold(),
+            width = theme_width
+        )?;
+    } else {
+        writeln!(
+            out,
+            "{:<width$} {}",
+            "Theme".bold(),
+            "Count".bold(),
+            width = theme_width
+        )?;
+    }
+    writeln!(out, "{}", "─".repeat(total_width))?;
+
+    // Print results
+    let total_results = results.len() as f64;
+    for (theme, count) in &sorted_themes {
+        let theme = truncate_with_ellipsis(theme, theme_width - 2);
+        if percent {
+            let share = format!("{:.1}%", f64::from(*count) / total_results * 100.0);
+            writeln!(
+                out,
+                "{:<width$} {:>6} {:>7}",
+                theme.cyan(),
+                count.to_string().green().bold(),
+                share.yellow(),
+                width = theme_width
+            )?;
+        } else {
+            writeln!(
+                out,
+                "{:<width$} {}",
+                theme.cyan(),
+                count.to_string().green().bold(),
+                width = theme_width
+            )?;
+        }
+    }
+
+    writeln!(out, "{}", "─".repeat(total_width))?;
+    writeln!(
+        out,
+        "Total unique themes: {}",
+        sorted_themes.len().to_string().yellow().bold()
+    )?;
+
+    Ok(())
+}
+
+/// Print results as an RSS 2.0 feed so a search can be subscribed to in a feed reader
+fn print_rss(
+    out: &mut impl Write,
+    results: &[mediathekviewweb::models::Item],
+    query: &str,
+) -> Result<()> {
+    let mut content = String::with_capacity(1024 + results.len() * 512);
+
+    content.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    content.push_str("<rss version=\"2.0\">\n");
+    content.push_str("  <channel>\n");
+    content.push_str("    <title>MediathekView Search: ");
+    content.push_str(&escape_xml(query));
+    content.push_str("</title>\n");
+    content.push_str("    <link>https://mediathekviewweb.de/</link>\n");
+    content.push_str("    <description>MWB - MediathekViewWeb CLI search results</description>\n");
+    content.push_str("    <lastBuildDate>");
+    content.push_str(&chrono::Utc::now().to_rfc2822());
+    content.push_str("</lastBuildDate>\n");
+
+    for entry in results {
+        content.push_str("    <item>\n");
+        content.push_str("      <title>");
+        content.push_str(&escape_xml(&entry.title));
+        content.push_str("</title>\n");
+        content.push_str("      <link>");
+        content.push_str(&escape_xml(&entry.url_video));
+        content.push_str("</link>\n");
+        content.push_str("      <guid isPermaLink=\"false\">");
+        content.push_str(&escape_xml(&entry.url_video));
+        content.push_str("</guid>\n");
+        if let Some(dt) = DateTime::from_timestamp(entry.timestamp, 0) {
+            content.push_str("      <pubDate>");
+            content.push_str(&dt.to_rfc2822());
+            content.push_str("</pubDate>\n");
+        }
+        content.push_str("      <category>");
+        content.push_str(&escape_xml(&entry.topic));
+        content.push_str("</category>\n");
+        if let Some(description) = &entry.description {
+            if !description.is_empty() {
+                content.push_str("      <description>");
+                content.push_str(&escape_xml(description));
+                content.push_str("</description>\n");
+            }
+        }
+        content.push_str("    </item>\n");
+    }
+
+    content.push_str("  </channel>\n");
+    content.push_str("</rss>\n");
+
+    write!(out, "{content}")?;
+
+    Ok(())
+}
+
+/// Print the results as an Atom 1.0 feed, the RSS feed's counterpart for
+/// readers that prefer Atom
+///
+/// Entries use the video URL as their ID like the RSS `guid`, and the channel
+/// as author, which Atom requires.
+fn print_atom(
+    out: &mut impl Write,
+    results: &[mediathekviewweb::models::Item],
+    query: &str,
+) -> Result<()> {
+    let mut content = String::with_capacity(1024 + results.len() * 512);
+    let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+
+    content.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    content.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    content.push_str("  <title>MediathekView Search: ");
+    content.push_str(&escape_xml(query));
+    content.push_str("</title>\n");
+    content.push_str("  <subtitle>MWB - MediathekViewWeb CLI search results</subtitle>\n");
+    content.push_str("  <id>https://mediathekviewweb.de/#query=");
+    content.push_str(&escape_xml(&urlencoding::encode(query)));
+    content.push_str("</id>\n");
+    content.push_str("  <link href=\"https://mediathekviewweb.de/\"/>\n");
+    content.push_str("  <updated>");
+    content.push_str(&now);
+    content.push_str("</updated>\n");
+    content.push_str("  <generator>mwb</generator>\n");
+
+    for entry in results {
+        let published = DateTime::from_timestamp(entry.timestamp, 0)
+            .map(|dt| dt.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+            .unwrap_or_else(|| now.clone());
+
+        content.push_str("  <entry>\n");
+        content.push_str("    <title>");
+        content.push_str(&escape_xml(&entry.title));
+        content.push_str("</title>\n");
+        content.push_str("    <link href=\"");
+        content.push_str(&escape_xml(&entry.url_video));
+        content.push_str("\"/>\n");
+        content.push_str("    <id>");
+        content.push_str(&escape_xml(&entry.url_video));
+        content.push_str("</id>\n");
+        content.push_str("    <published>");
+        content.push_str(&published);
+        content.push_str("</published>\n");
+        content.push_str("    <updated>");
+        content.push_str(&published);
+        content.push_str("</updated>\n");
+        content.push_str("    <author><name>");
+        content.push_str(&escape_xml(&entry.channel));
+        content.push_str("</name></author>\n");
+        content.push_str("    <category term=\"");
+        content.push_str(&escape_xml(&entry.topic));
+        content.push_str("\"/>\n");
+        if let Some(description) = &entry.description {
+            if !description.is_empty() {
+                content.push_str("    <summary>");
+                content.push_str(&escape_xml(description));
+                content.push_str("</summary>\n");
+            }
+        }
+        content.push_str("  </entry>\n");
+    }
+
+    content.push_str("</feed>\n");
+
+    write!(out, "{content}")?;
 
-        // Format: [Channel] Title (Date) [Duration] - Theme
-        println!(
-            "[{}] {} ({}) {} - {}",
-            entry.channel.bright_cyan(),
-            entry.title.bright_white(),
-            date.yellow(),
-            if duration.is_empty() {
-                "".to_string()
-            } else {
-                format!("[{}]", duration.green())
-            },
-            entry.topic.bright_magenta()
-        );
-    }
+    Ok(())
 }
 
-fn print_theme_count_table(results: &[mediathekviewweb::models::Item]) {
-    use std::collections::HashMap;
+fn print_xspf(
+    out: &mut impl Write,
+    results: &[mediathekviewweb::models::Item],
+    query: &str,
+    minimal: bool,
+    compact: bool,
+) -> Result<()> {
+    let xspf_content = generate_xspf_content(results, query, "m", minimal, compact);
+    writeln!(out, "{xspf_content}")?;
+
+    Ok(())
+}
+
+/// Print the results as an Apple property-list playlist
+///
+/// Uses the iTunes library key names (`Name`, `Artist`, `Album`, `Location`,
+/// `Total Time` in milliseconds) that macOS players understand.
+fn print_plist(
+    out: &mut impl Write,
+    results: &[mediathekviewweb::models::Item],
+    query: &str,
+    quality: &str,
+) -> Result<()> {
+    let mut content = String::with_capacity(512 + results.len() * 512);
+
+    content.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    content.push_str("<!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n");
+    content.push_str("<plist version=\"1.0\">\n");
+    content.push_str("<dict>\n");
+    content.push_str("  <key>Name</key>\n");
+    content.push_str("  <string>");
+    content.push_str(&escape_xml(&format!("MediathekView Search: {query}")));
+    content.push_str("</string>\n");
+    content.push_str("  <key>Tracks</key>\n");
+    content.push_str("  <array>\n");
 
-    // Count themes
-    let mut theme_counts: HashMap<String, u32> = HashMap::new();
     for entry in results {
-        *theme_counts.entry(entry.topic.clone()).or_insert(0) += 1;
+        content.push_str("    <dict>\n");
+        for (key, value) in [
+            ("Name", entry.title.as_str()),
+            ("Artist", entry.channel.as_str()),
+            ("Album", entry.topic.as_str()),
+            ("Location", select_video_url(entry, quality)),
+        ] {
+            content.push_str("      <key>");
+            content.push_str(key);
+            content.push_str("</key>\n");
+            content.push_str("      <string>");
+            content.push_str(&escape_xml(value));
+            content.push_str("</string>\n");
+        }
+        if let Some(duration) = entry.duration {
+            content.push_str("      <key>Total Time</key>\n");
+            content.push_str("      <integer>");
+            content.push_str(&duration.as_millis().to_string());
+            content.push_str("</integer>\n");
+        }
+        if let Some(date) = DateTime::from_timestamp(entry.timestamp, 0) {
+            content.push_str("      <key>Date Added</key>\n");
+            content.push_str("      <date>");
+            content.push_str(&date.format("%Y-%m-%dT%H:%M:%SZ").to_string());
+            content.push_str("</date>\n");
+        }
+        content.push_str("    </dict>\n");
     }
 
-    // Convert to vector and sort by count (descending)
-    let mut sorted_themes: Vec<(String, u32)> = theme_counts.into_iter().collect();
-    sorted_themes.sort_by(|a, b| b.1.cmp(&a.1));
+    content.push_str("  </array>\n");
+    content.push_str("</dict>\n");
+    content.push_str("</plist>\n");
 
-    if sorted_themes.is_empty() {
-        println!("No themes found.");
-        return;
-    }
+    write!(out, "{content}")?;
 
-    // Calculate optimal column width based on longest theme name
-    let max_theme_length = sorted_themes
-        .iter()
-        .map(|(theme, _)| theme.len())
-        .max()
-        .unwrap_or(10);
-    let theme_width = std::cmp::max(max_theme_length + 2, 25); // Minimum 25 chars for "Theme" header
-    let total_width = theme_width + 10; // +10 for count column and spacing
+    Ok(())
+}
 
-    // Print header
-    println!("{}", "Theme Count Report".bold().underline());
-    println!("{}", "─".repeat(total_width));
-    println!(
-        "{:<width$} {}",
-        "Theme".bold(),
-        "Count".bold(),
-        width = theme_width
-    );
-    println!("{}", "─".repeat(total_width));
+/// Print the results as a standalone HTML page with a table of links
+///
+/// Meant to be written with `--output` and opened in a browser or sent to
+/// someone. The header carries the same totals as the table format.
+fn print_html(
+    out: &mut impl Write,
+    results: &[mediathekviewweb::models::Item],
+    query_info: &mediathekviewweb::models::QueryInfo,
+    query: &str,
+    quality: &str,
+) -> Result<()> {
+    let title = escape_xml(&format!("MediathekView Search: {query}"));
+    let mut content = String::with_capacity(2048 + results.len() * 512);
+
+    content.push_str("<!DOCTYPE html>\n");
+    content.push_str("<html lang=\"de\">\n");
+    content.push_str("<head>\n");
+    content.push_str("  <meta charset=\"utf-8\">\n");
+    content.push_str("  <title>");
+    content.push_str(&title);
+    content.push_str("</title>\n");
+    content.push_str("  <style>\n");
+    content.push_str("    body { font-family: sans-serif; margin: 2em; color: #222; }\n");
+    content.push_str("    table { border-collapse: collapse; width: 100%; }\n");
+    content.push_str("    th, td { padding: 0.4em 0.8em; border-bottom: 1px solid #ddd; text-align: left; vertical-align: top; }\n");
+    content.push_str("    th { background: #f0f0f0; }\n");
+    content.push_str("    tr:hover td { background: #fafafa; }\n");
+    content.push_str("    .meta { color: #666; }\n");
+    content.push_str("    .nowrap { white-space: nowrap; }\n");
+    content.push_str("  </style>\n");
+    content.push_str("</head>\n");
+    content.push_str("<body>\n");
+    content.push_str("  <h1>");
+    content.push_str(&title);
+    content.push_str("</h1>\n");
+    content.push_str(&format!(
+        "  <p class=\"meta\">Total results: {} &middot; Showing: {} &middot; Search time: {}ms</p>\n",
+        query_info.total_results,
+        results.len(),
+        query_info.search_engine_time.as_millis()
+    ));
 
-    // Print results
-    for (theme, count) in &sorted_themes {
-        println!(
-            "{:<width$} {}",
-            theme.cyan(),
-            count.to_string().green().bold(),
-            width = theme_width
-        );
+    if results.is_empty() {
+        content.push_str("  <p>No results found.</p>\n");
+    } else {
+        content.push_str("  <table>\n");
+        content.push_str("    <tr><th>Channel</th><th>Topic</th><th>Title</th><th>Date</th><th>Duration</th><th>Video</th></tr>\n");
+
+        for entry in results {
+            let date = DateTime::from_timestamp(entry.timestamp, 0)
+                .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_default();
+            let duration = entry
+                .duration
+                .map_or(String::new(), |d| format!("{}min", d.as_secs() / 60));
+
+            content.push_str("    <tr>");
+            for cell in [&entry.channel, &entry.topic, &entry.title] {
+                content.push_str("<td>");
+                content.push_str(&escape_xml(cell));
+                content.push_str("</td>");
+            }
+            content.push_str("<td class=\"nowrap\">");
+            content.push_str(&date);
+            content.push_str("</td><td class=\"nowrap\">");
+            content.push_str(&duration);
+            content.push_str("</td><td><a href=\"");
+            content.push_str(&escape_xml(select_video_url(entry, quality)));
+            content.push_str("\">Play</a></td></tr>\n");
+        }
+
+        content.push_str("  </table>\n");
     }
 
-    println!("{}", "─".repeat(total_width));
-    println!(
-        "Total unique themes: {}",
-        sorted_themes.len().to_string().yellow().bold()
-    );
-}
+    content.push_str("</body>\n");
+    content.push_str("</html>\n");
+
+    write!(out, "{content}")?;
 
-fn print_xspf(results: &[mediathekviewweb::models::Item], query: &str) {
-    let xspf_content = generate_xspf_content(results, query, "m");
-    println!("{xspf_content}");
+    Ok(())
 }
 
 /// Generates complete XSPF playlist content as a string
@@ -1272,6 +4772,9 @@ fn print_xspf(results: &[mediathekviewweb::models::Item], query: &str) {
 /// # Arguments
 /// * `results` - Array of `MediathekView` items to include in playlist
 /// * `query` - Search query string used for playlist title
+/// * `quality` - Video quality to select (l, m, h)
+/// * `minimal` - Emit only `<title>` and `<location>` per track for picky players
+/// * `compact` - Omit the indentation and newlines between elements
 ///
 /// # Returns
 /// * `Result<String>` - Complete XSPF XML content or error
@@ -1279,20 +4782,49 @@ fn generate_xspf_content(
     results: &[mediathekviewweb::models::Item],
     query: &str,
     quality: &str,
+    minimal: bool,
+    compact: bool,
 ) -> String {
     // Pre-allocate capacity to reduce reallocations (header + ~512 chars per track)
     let mut content = String::with_capacity(1024 + results.len() * 512);
 
-    content.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
-    content.push_str("<playlist version=\"1\" xmlns=\"http://xspf.org/ns/0/\">\n");
-    content.push_str("  <title>MediathekView Search: ");
-    content.push_str(&escape_xml(query));
-    content.push_str("</title>\n");
-    content.push_str("  <creator>MWB - MediathekViewWeb CLI</creator>\n");
-    content.push_str("  <date>");
-    content.push_str(&chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string());
-    content.push_str("</date>\n");
-    content.push_str("  <trackList>\n");
+    // Indentation per nesting depth; compact output has no whitespace between elements
+    let (nl, i1, i2, i3) = if compact {
+        ("", "", "", "")
+    } else {
+        ("\n", "  ", "    ", "      ")
+    };
+
+    content.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>");
+    content.push_str(nl);
+    content.push_str(
+        "<playlist version=\"1\" xmlns=\"http://xspf.org/ns/0/\" xmlns:vlc=\"http://www.videolan.org/vlc/playlist/ns/0/\">",
+    );
+    content.push_str(nl);
+    push_xml_element(
+        &mut content,
+        i1,
+        "title",
+        &format!("MediathekView Search: {query}"),
+        nl,
+    );
+    push_xml_element(
+        &mut content,
+        i1,
+        "creator",
+        "MWB - MediathekViewWeb CLI",
+        nl,
+    );
+    push_xml_element(
+        &mut content,
+        i1,
+        "date",
+        &chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+        nl,
+    );
+    content.push_str(i1);
+    content.push_str("<trackList>");
+    content.push_str(nl);
 
     // Generate track entries with metadata
     for entry in results {
@@ -1303,56 +4835,140 @@ fn generate_xspf_content(
             .map(|dt| dt.format("%Y-%m-%d").to_string())
             .unwrap_or_default();
 
-        content.push_str("    <track>\n");
+        content.push_str(i2);
+        content.push_str("<track>");
+        content.push_str(nl);
         // Include date in title for VLC visibility
         let title_with_date = if date_readable.is_empty() {
             entry.title.clone()
         } else {
             format!("{} ({date_readable})", entry.title)
         };
-        content.push_str("      <title>");
-        content.push_str(&escape_xml(&title_with_date));
-        content.push_str("</title>\n");
-        // Use creator for channel, artist for date (VLC displays artist column)
-        content.push_str("      <creator>");
-        content.push_str(&escape_xml(&entry.channel));
-        content.push_str("</creator>\n");
-        content.push_str("      <artist>");
-        content.push_str(&escape_xml(&date_readable));
-        content.push_str("</artist>\n");
-        content.push_str("      <album>");
-        content.push_str(&escape_xml(&entry.topic));
-        content.push_str("</album>\n");
+        push_xml_element(&mut content, i3, "title", &title_with_date, nl);
         // Select video URL based on quality parameter
-        let video_url = match quality {
-            "l" | "low" => entry.url_video_low.as_ref().unwrap_or(&entry.url_video),
-            "h" | "hd" | "high" => entry.url_video_hd.as_ref().unwrap_or(&entry.url_video),
-            _ => &entry.url_video, // default to medium quality
-        };
-        content.push_str("      <location>");
-        content.push_str(&escape_xml(video_url));
-        content.push_str("</location>\n");
-        if duration_ms > 0 {
-            content.push_str("      <duration>");
-            content.push_str(&duration_ms.to_string());
-            content.push_str("</duration>\n");
-        }
-        if let Some(description) = &entry.description {
-            if !description.is_empty() {
-                content.push_str("      <annotation>");
-                content.push_str(&escape_xml(description));
-                content.push_str("</annotation>\n");
+        let video_url = select_video_url(entry, quality);
+        if minimal {
+            push_xml_element(&mut content, i3, "location", video_url, nl);
+        } else {
+            // Use creator for channel, artist for date (VLC displays artist column)
+            push_xml_element(&mut content, i3, "creator", &entry.channel, nl);
+            push_xml_element(&mut content, i3, "artist", &date_readable, nl);
+            push_xml_element(&mut content, i3, "album", &entry.topic, nl);
+            push_xml_element(&mut content, i3, "location", video_url, nl);
+            if duration_ms > 0 {
+                push_xml_element(&mut content, i3, "duration", &duration_ms.to_string(), nl);
+            }
+            if let Some(description) = &entry.description {
+                if !description.is_empty() {
+                    push_xml_element(&mut content, i3, "annotation", description, nl);
+                }
+            }
+            // VLC loads the captions from its playlist extension
+            if let Some(subtitle) = subtitle_url(entry) {
+                content.push_str(i3);
+                content
+                    .push_str("<extension application=\"http://www.videolan.org/vlc/playlist/0\">");
+                content.push_str(nl);
+                push_xml_element(
+                    &mut content,
+                    &format!("{i3}{i1}"),
+                    "vlc:option",
+                    &format!("sub-file={subtitle}"),
+                    nl,
+                );
+                content.push_str(i3);
+                content.push_str("</extension>");
+                content.push_str(nl);
             }
         }
-        content.push_str("    </track>\n");
+        content.push_str(i2);
+        content.push_str("</track>");
+        content.push_str(nl);
     }
 
-    content.push_str("  </trackList>\n");
+    content.push_str(i1);
+    content.push_str("</trackList>");
+    content.push_str(nl);
     content.push_str("</playlist>\n");
 
     content
 }
 
+/// Append `<tag>value</tag>` with the value XML-escaped
+fn push_xml_element(content: &mut String, indent: &str, tag: &str, value: &str, newline: &str) {
+    content.push_str(indent);
+    content.push('<');
+    content.push_str(tag);
+    content.push('>');
+    content.push_str(&escape_xml(value));
+    content.push_str("</");
+    content.push_str(tag);
+    content.push('>');
+    content.push_str(newline);
+}
+
+/// Normalize a user-supplied quality option, warning and using medium for invalid values
+fn validate_quality(quality: &str) -> &'static str {
+    match quality {
+        "l" | "low" => "l",
+        "h" | "hd" | "high" => "h",
+        "m" | "medium" | "" => "m",
+        _ => {
+            println!("{}", format!("Warning: Invalid quality '{quality}'. Using medium quality (m). Valid options: l (low), m (medium), h (HD)").yellow());
+            "m"
+        }
+    }
+}
+
+/// Pick the video URL for the requested quality, falling back to medium quality
+fn select_video_url<'a>(entry: &'a mediathekviewweb::models::Item, quality: &str) -> &'a str {
+    quality_url(entry, quality).unwrap_or(&entry.url_video)
+}
+
+/// The video URL in exactly the requested quality, `None` when the entry has no such version
+fn quality_url<'a>(entry: &'a mediathekviewweb::models::Item, quality: &str) -> Option<&'a str> {
+    let url = match quality {
+        "l" | "low" => entry.url_video_low.as_deref(),
+        "h" | "hd" | "high" => entry.url_video_hd.as_deref(),
+        _ => Some(entry.url_video.as_str()), // default to medium quality
+    };
+    url.filter(|url| !url.trim().is_empty())
+}
+
+/// Human-readable name of a validated quality
+fn quality_name(quality: &str) -> &'static str {
+    match quality {
+        "l" => "low",
+        "h" => "HD",
+        _ => "medium",
+    }
+}
+
+/// Log every result that falls back to medium quality, returning how many do
+fn log_quality_fallbacks(results: &[mediathekviewweb::models::Item], quality: &str) -> usize {
+    let mut downgraded = 0;
+    for entry in results {
+        if quality_url(entry, quality).is_none() {
+            tracing::info!(
+                channel = %entry.channel,
+                title = %entry.title,
+                requested = %quality_name(quality),
+                "Requested quality not available, using medium quality"
+            );
+            downgraded += 1;
+        }
+    }
+    downgraded
+}
+
+/// Subtitle URL of an entry, the API sends an empty string when there is none
+fn subtitle_url(entry: &mediathekviewweb::models::Item) -> Option<&str> {
+    entry
+        .url_subtitle
+        .as_deref()
+        .filter(|url| !url.trim().is_empty())
+}
+
 fn escape_xml(text: &str) -> String {
     text.replace('&', "&amp;")
         .replace('<', "&lt;")
@@ -1364,6 +4980,9 @@ fn escape_xml(text: &str) -> String {
 fn save_xspf_playlist(
     results: &[mediathekviewweb::models::Item],
     query_terms: &[String],
+    minimal: bool,
+    compact: bool,
+    edit: bool,
 ) -> Result<()> {
     if results.is_empty() {
         println!("{}", "No results found to save to playlist.".yellow());
@@ -1371,10 +4990,11 @@ fn save_xspf_playlist(
     }
 
     // Create playlist filename from query (similar to VLC playlist naming)
-    let playlist_name = generate_xspf_filename(&query_terms.join(" "));
+    let playlist_name = playlist_filename(Path::new("."), &query_terms.join(" "), false);
 
     // Generate XSPF content
-    let xspf_content = generate_xspf_content(results, &query_terms.join(" "), "m");
+    let xspf_content =
+        generate_xspf_content(results, &query_terms.join(" "), "m", minimal, compact);
 
     // Write to file
     let mut file = File::create(&playlist_name)?;
@@ -1389,11 +5009,17 @@ fn save_xspf_playlist(
         format!("Added {} track(s) to playlist", results.len()).green()
     );
 
+    if edit {
+        drop(file);
+        open_in_editor(&playlist_name)?;
+    }
+
     Ok(())
 }
 
-fn generate_xspf_filename(query: &str) -> String {
-    // Similar to M3U playlist naming but with .xspf extension
+/// The query-based part of the `-x` playlist name, without extension
+fn xspf_stem(query: &str) -> String {
+    // Similar to the -v playlist naming, but keeping the first 3 words
     let sanitized_query = query
         .chars()
         .filter(|c| c.is_alphanumeric() || c.is_whitespace())
@@ -1403,11 +5029,529 @@ fn generate_xspf_filename(query: &str) -> String {
         .collect::<Vec<_>>()
         .join("_");
 
-    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
-
     if sanitized_query.is_empty() {
-        format!("mwb_playlist_{timestamp}.xspf")
+        "mwb_playlist".to_string()
     } else {
-        format!("mwb_{sanitized_query}_{timestamp}.xspf")
+        format!("mwb_{sanitized_query}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mediathekviewweb::models::Item;
+    use std::time::Duration;
+
+    /// A result with the fields the formatters read, the rest left empty
+    fn item(channel: &str, topic: &str, title: &str, minutes: u64, timestamp: i64) -> Item {
+        Item {
+            channel: channel.to_string(),
+            topic: topic.to_string(),
+            title: title.to_string(),
+            description: None,
+            timestamp,
+            duration: Some(Duration::from_secs(minutes * 60)),
+            size: None,
+            url_website: String::new(),
+            url_subtitle: None,
+            url_video: format!("https://example.org/{}.mp4", timestamp),
+            url_video_low: None,
+            url_video_hd: None,
+            filmliste_timestamp: 0,
+            id: timestamp.to_string(),
+        }
+    }
+
+    fn sample() -> Vec<Item> {
+        vec![
+            item("ARD", "Tatort", "Der Fall", 90, 1_700_000_000),
+            item("ZDF", "Terra X", "Die Römer", 45, 1_700_003_600),
+        ]
+    }
+
+    /// Run a formatter into a buffer, without colors
+    fn render(print: impl FnOnce(&mut Vec<u8>) -> Result<()>) -> Vec<u8> {
+        colored::control::set_override(false);
+        let mut out = Vec::new();
+        print(&mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn csv_writes_header_and_crlf_records() {
+        let out = render(|out| print_csv(out, &sample(), false));
+        assert_eq!(
+            out,
+            b"Channel,Theme,Title,Duration,Date,URL,Description\r\n\
+              \"ARD\",\"Tatort\",\"Der Fall\",\"5400\",\"2023-11-14 22:13\",\"https://example.org/1700000000.mp4\",\"\"\r\n\
+              \"ZDF\",\"Terra X\",\"Die R\xc3\xb6mer\",\"2700\",\"2023-11-14 23:13\",\"https://example.org/1700003600.mp4\",\"\"\r\n"
+        );
+    }
+
+    #[test]
+    fn csv_starts_with_bom_when_requested() {
+        let out = render(|out| print_csv(out, &[], true));
+        assert!(out.starts_with("\u{feff}Channel,".as_bytes()));
+    }
+
+    #[test]
+    fn oneline_prints_one_line_per_result() {
+        let out = render(|out| print_oneline(out, &sample(), None));
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "[ARD] Der Fall (2023-11-14 22:13) [90min] - https://example.org/1700000000.mp4\n\
+             [ZDF] Die Römer (2023-11-14 23:13) [45min] - https://example.org/1700003600.mp4\n"
+        );
+    }
+
+    #[test]
+    fn oneline_theme_ends_with_the_topic_and_truncates_titles() {
+        let out = render(|out| print_oneline_theme(out, &sample(), Some(6)));
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "[ARD] Der F… (2023-11-14 22:13) [90min] - Tatort\n\
+             [ZDF] Die R… (2023-11-14 23:13) [45min] - Terra X\n"
+        );
+    }
+
+    #[test]
+    fn brief_numbers_titles() {
+        let out = render(|out| print_brief(out, &sample()));
+        assert_eq!(out, "1. Der Fall\n2. Die Römer\n".as_bytes());
+    }
+
+    #[test]
+    fn table_prints_each_result_with_its_fields() {
+        let query_info = mediathekviewweb::models::QueryInfo {
+            filmliste_timestamp: 0,
+            result_count: 2,
+            search_engine_time: Duration::from_millis(12),
+            total_results: 40,
+        };
+        let out = render(|out| print_table(out, &sample(), &query_info, false));
+        let text = String::from_utf8(out).unwrap();
+
+        assert!(text
+            .starts_with("Search Results\nTotal results: 40\nShowing: 2\nSearch time: 12ms\n\n"));
+        assert!(text.contains(
+            "Channel: ARD\nTheme: Tatort\nTitle: Der Fall\nDuration: 1h 30m 0s\n\
+             Date: 2023-11-14 22:13\nVideo URL: https://example.org/1700000000.mp4\n"
+        ));
+        assert!(text.contains("Duration: 45m 0s\n"));
+    }
+
+    #[test]
+    fn table_reports_no_results() {
+        let query_info = mediathekviewweb::models::QueryInfo {
+            filmliste_timestamp: 0,
+            result_count: 0,
+            search_engine_time: Duration::from_millis(0),
+            total_results: 0,
+        };
+        let out = render(|out| print_table(out, &[], &query_info, false));
+        assert!(out.ends_with(b"\nNo results found.\n"));
+    }
+
+    #[test]
+    fn theme_count_table_sorts_by_count() {
+        let mut results = sample();
+        results.push(item("ARD", "Tatort", "Der andere Fall", 90, 1_700_007_200));
+        let out = render(|out| print_theme_count_table(out, &results, true));
+        let text = String::from_utf8(out).unwrap();
+
+        let tatort = text.find("Tatort").unwrap();
+        let terra_x = text.find("Terra X").unwrap();
+        assert!(tatort < terra_x);
+        assert!(text.contains("66.7%"));
+        assert!(text.contains("33.3%"));
+        assert!(text.ends_with("Total unique themes: 2\n"));
+    }
+
+    #[test]
+    fn ndjson_writes_one_object_per_line() {
+        let out = render(|out| print_ndjson(out, &sample()));
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        let first: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(first["title"], "Der Fall");
+        assert_eq!(first["channel"], "ARD");
+    }
+
+    #[test]
+    fn normalize_query_terms_strips_quotes_and_whitespace() {
+        let terms = vec![
+            "  Tatort ".to_string(),
+            "\"Terra X\"".to_string(),
+            "' \"Die Anstalt\" '".to_string(),
+            "\"\"".to_string(),
+            "   ".to_string(),
+            "Sendung mit \"Maus\"".to_string(),
+        ];
+
+        assert_eq!(
+            normalize_query_terms(terms),
+            vec!["Tatort", "Terra X", "Die Anstalt", "Sendung mit \"Maus\""]
+        );
+    }
+
+    /// `--vlc-ai` values of a `mwb search` command line
+    fn parse_vlc_ai(args: &[&str]) -> (Vec<String>, Option<Vec<String>>) {
+        let cli = Cli::try_parse_from(["mwb", "search"].iter().chain(args)).unwrap();
+        let Commands::Search(search) = cli.command else {
+            panic!("expected the search command");
+        };
+        (search.query, search.vlc_ai)
+    }
+
+    #[test]
+    fn vlc_ai_parses_bare_clipboard_and_text_values() {
+        assert_eq!(
+            parse_vlc_ai(&["Tatort", "--vlc-ai"]),
+            (vec!["Tatort".to_string()], Some(vec![String::new()]))
+        );
+        assert_eq!(
+            parse_vlc_ai(&["Tatort", "--vlc-ai=clipboard"]).1,
+            Some(vec!["clipboard".to_string()])
+        );
+        assert_eq!(
+            parse_vlc_ai(&["Tatort", "--vlc-ai=nur Münster-Folgen", "--vlc-ai"]).1,
+            Some(vec!["nur Münster-Folgen".to_string(), String::new()])
+        );
+        assert_eq!(parse_vlc_ai(&["Tatort"]).1, None);
+    }
+
+    #[test]
+    fn vlc_ai_value_must_be_attached_with_equals() {
+        // A separate word is a query term, not the flag's value
+        assert_eq!(
+            parse_vlc_ai(&["--vlc-ai", "clipboard"]),
+            (vec!["clipboard".to_string()], Some(vec![String::new()]))
+        );
+    }
+
+    #[test]
+    fn bare_vlc_ai_means_no_search_info() {
+        assert_eq!(get_search_info("").unwrap(), None);
+        assert_eq!(get_search_info("  ").unwrap(), None);
+        assert_eq!(
+            get_search_info(" Ostfriesenkrimis ").unwrap(),
+            Some("Ostfriesenkrimis".to_string())
+        );
+        assert_eq!(
+            get_search_hints(&[String::new(), "Tatort".to_string()]).unwrap(),
+            vec!["Tatort".to_string()]
+        );
+    }
+
+    #[test]
+    fn expand_path_replaces_tilde_and_variables() {
+        let home = dirs::home_dir().unwrap().to_string_lossy().to_string();
+        // Reading variables that are already set, tests must not write the environment
+        let path = std::env::var("PATH").unwrap();
+
+        assert_eq!(expand_path("~"), PathBuf::from(&home));
+        assert_eq!(
+            expand_path("~/Videos/mwb"),
+            PathBuf::from(format!("{home}/Videos/mwb"))
+        );
+        assert_eq!(expand_path("$PATH/x"), PathBuf::from(format!("{path}/x")));
+        assert_eq!(expand_path("${PATH}x"), PathBuf::from(format!("{path}x")));
+        assert_eq!(
+            expand_path("%PATH%\\x"),
+            PathBuf::from(format!("{path}\\x"))
+        );
+    }
+
+    #[test]
+    fn expand_path_keeps_unknown_references() {
+        assert_eq!(
+            expand_path("$MWB_TEST_UNSET_VARIABLE/a ~/b 100%"),
+            PathBuf::from("$MWB_TEST_UNSET_VARIABLE/a ~/b 100%")
+        );
+        assert_eq!(expand_path("${unclosed"), PathBuf::from("${unclosed"));
+    }
+
+    #[test]
+    fn clipboard_preview_cuts_at_character_boundaries() {
+        // Byte 50 falls inside the 'ä'
+        let text = format!("{}äöüß Folge 1: Käthe", "x".repeat(49));
+        let preview = clipboard_preview(&text);
+
+        assert_eq!(preview, format!("{}ä...", "x".repeat(49)));
+        assert_eq!(clipboard_preview("Käthe"), "Käthe");
+    }
+
+    #[test]
+    fn vlc_playlist_filename_survives_long_multibyte_queries() {
+        let query = "Läßt Öl über Äcker fließen – Grüße aus München, Köln & Düsseldorf!!";
+        assert!(query.chars().count() >= 60);
+
+        let stem = vlc_playlist_stem(query);
+        assert!(stem.starts_with("mwb_L"));
+        assert!(stem.is_ascii());
+
+        assert_eq!(vlc_playlist_stem(&"ä".repeat(60)), "mwb_playlist");
+    }
+
+    #[test]
+    fn printed_playlist_filename_is_the_one_written() {
+        let dir = std::env::temp_dir().join(format!("mwb-playlist-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        // Asking again, as the writer does after --print-filename, gives the same name
+        let printed = playlist_filename(&dir, "tatort >85", true);
+        assert_eq!(printed, "mwb_tatort_m85.xspf");
+        assert_eq!(playlist_filename(&dir, "tatort >85", true), printed);
+        assert_eq!(
+            playlist_filename(&dir, "Tatort Münster >80", false),
+            "mwb_Tatort_Münster_80.xspf"
+        );
+
+        // An existing playlist is never overwritten
+        std::fs::write(dir.join(&printed), "").unwrap();
+        let next = playlist_filename(&dir, "tatort >85", true);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(next, "mwb_tatort_m85_2.xspf");
+    }
+
+    #[test]
+    fn csv_field_quotes_commas_quotes_and_newlines() {
+        assert_eq!(csv_field("Tatort, Münster"), "\"Tatort, Münster\"");
+        assert_eq!(csv_field("Der \"Fall\""), "\"Der \"\"Fall\"\"\"");
+        assert_eq!(
+            csv_field("Zeile 1\r\nZeile 2\rZeile 3"),
+            "\"Zeile 1\nZeile 2\nZeile 3\""
+        );
+    }
+
+    #[test]
+    fn csv_record_keeps_multiline_fields_in_one_record() {
+        let mut entry = item("ARD", "Tatort", "Der \"Fall\"", 90, 1_700_000_000);
+        entry.description = Some("Erster Absatz, mit Komma.\r\nZweiter Absatz".to_string());
+        let out = render(|out| print_csv(out, &[entry], false));
+        let text = String::from_utf8(out).unwrap();
+
+        // The header and one record, each ended by CRLF; the bare LF stays inside the quotes
+        assert_eq!(text.matches("\r\n").count(), 2);
+        assert!(text.ends_with(
+            ",\"Der \"\"Fall\"\"\",\"5400\",\"2023-11-14 22:13\",\"https://example.org/1700000000.mp4\",\"Erster Absatz, mit Komma.\nZweiter Absatz\"\r\n"
+        ));
+    }
+
+    #[test]
+    fn natural_cmp_orders_numbers_by_value() {
+        use std::cmp::Ordering;
+
+        assert_eq!(natural_cmp("Folge 2", "Folge 10"), Ordering::Less);
+        assert_eq!(natural_cmp("Folge 10", "Folge 9"), Ordering::Greater);
+        assert_eq!(natural_cmp("Folge 007", "Folge 7"), Ordering::Less);
+        assert_eq!(natural_cmp("folge 3", "Folge 4"), Ordering::Less);
+
+        let mut titles = vec!["Folge 10", "Folge 1", "Folge 2", "Folge 100"];
+        titles.sort_by(|a, b| natural_cmp(a, b));
+        assert_eq!(titles, ["Folge 1", "Folge 2", "Folge 10", "Folge 100"]);
+    }
+
+    #[test]
+    fn extract_duration_selectors_reads_bounds_and_units() {
+        let minutes = |n: u64| Some(Duration::from_secs(n * 60));
+
+        assert_eq!(
+            extract_duration_selectors("Tatort >90"),
+            (
+                "Tatort".to_string(),
+                DurationRange {
+                    min: minutes(90),
+                    max: None
+                }
+            )
+        );
+        assert_eq!(
+            extract_duration_selectors("<30 Terra X").1,
+            DurationRange {
+                min: None,
+                max: minutes(30)
+            }
+        );
+        assert_eq!(
+            extract_duration_selectors("#Tatort >60<120"),
+            (
+                "#Tatort".to_string(),
+                DurationRange {
+                    min: minutes(60),
+                    max: minutes(120)
+                }
+            )
+        );
+        assert_eq!(
+            extract_duration_selectors(">90m <2h >5400s").1,
+            DurationRange {
+                min: minutes(90),
+                max: minutes(120)
+            }
+        );
+    }
+
+    #[test]
+    fn extract_duration_selectors_keeps_other_tokens() {
+        assert_eq!(
+            extract_duration_selectors("90m a>b >x"),
+            ("90m a>b >x".to_string(), DurationRange::default())
+        );
+    }
+
+    #[test]
+    fn compile_filter_regex_honors_case_sensitivity() {
+        let sensitive = compile_filter_regex("Tatort", "Tatort", "include", 1, true).unwrap();
+        assert!(sensitive.is_match("Tatort: Der Fall"));
+        assert!(!sensitive.is_match("tatort"));
+
+        let insensitive = compile_filter_regex("Tatort", "Tatort", "include", 1, false).unwrap();
+        assert!(insensitive.is_match("TATORT"));
+    }
+
+    #[test]
+    fn compile_filter_regex_names_the_broken_pattern() {
+        let error = compile_filter_regex("(Tatort", "(Tatort", "exclude", 2, false).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("Invalid exclude pattern #2 '(Tatort': "));
+
+        let error = compile_filter_regex("a{1000}{1000}", "a{1000}{1000}", "include", 1, false)
+            .unwrap_err();
+        assert!(error.to_string().contains("is too complex"));
+    }
+
+    #[test]
+    fn compare_items_orders_equal_durations_reproducibly() {
+        let mut first = item("ARD", "Tatort", "A", 90, 1_700_000_000);
+        let mut second = item("ZDF", "Tatort", "B", 90, 1_700_000_000);
+        first.url_video = "https://example.org/a.mp4".to_string();
+        second.url_video = "https://example.org/b.mp4".to_string();
+        let newer = item("3sat", "Tatort", "C", 90, 1_700_100_000);
+        let shorter = item("ARD", "Tatort", "D", 45, 1_700_200_000);
+
+        let sorted_titles = |mut items: Vec<Item>, order: &str| {
+            items.sort_by(|a, b| compare_items(a, b, "duration", order));
+            items.into_iter().map(|i| i.title).collect::<Vec<_>>()
+        };
+
+        // Whatever order the API returned them in
+        let inputs = [
+            vec![
+                first.clone(),
+                second.clone(),
+                newer.clone(),
+                shorter.clone(),
+            ],
+            vec![
+                newer.clone(),
+                shorter.clone(),
+                second.clone(),
+                first.clone(),
+            ],
+            vec![second, newer, first, shorter],
+        ];
+        for input in inputs {
+            assert_eq!(sorted_titles(input.clone(), "asc"), ["D", "A", "B", "C"]);
+            assert_eq!(sorted_titles(input, "desc"), ["C", "B", "A", "D"]);
+        }
+    }
+
+    #[test]
+    fn duplicate_key_ignores_variant_markers_but_not_channels() {
+        let plain = item("ARD", "Tatort", "Der Fall", 90, 1_700_000_000);
+        let described = item(
+            "ard",
+            "Tatort",
+            "Der Fall (Audiodeskription)",
+            90,
+            1_700_000_000,
+        );
+        let clear = item(
+            "ARD",
+            "Tatort",
+            "Der  Fall - Klare Sprache",
+            90,
+            1_700_000_000,
+        );
+        let elsewhere = item("ONE", "Tatort", "Der Fall", 90, 1_700_000_000);
+
+        assert_eq!(duplicate_key(&plain), duplicate_key(&described));
+        assert_eq!(duplicate_key(&plain), duplicate_key(&clear));
+        assert_ne!(duplicate_key(&plain), duplicate_key(&elsewhere));
+        assert_eq!(normalize_title("Folge 3 (S2/E10) [HD]"), "folge 3 (s2/e10)");
+    }
+
+    #[test]
+    fn field_regex_errors_name_the_flag() {
+        let broken = |field| {
+            let patterns = [FieldPattern {
+                field,
+                pattern: "(".to_string(),
+            }];
+            apply_field_regex_filters(sample(), &patterns, &[], false, false, false)
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert!(broken(FilterField::Topic).starts_with("Invalid include-topic-regex pattern #1"));
+        assert!(
+            broken(FilterField::Channel).starts_with("Invalid include-channel-regex pattern #1")
+        );
+        assert!(broken(FilterField::Title).starts_with("Invalid include-title pattern #1"));
+    }
+
+    #[test]
+    fn yaml_lists_results_as_a_sequence() {
+        let out = render(|out| print_yaml(out, &sample(), None));
+        let text = String::from_utf8(out).unwrap();
+
+        assert!(text.starts_with("- "));
+        assert!(text.contains("title: Der Fall\n"));
+        assert!(text.contains("title: Die Römer\n"));
+        assert!(text.ends_with('\n') && !text.ends_with("\n\n"));
+    }
+
+    #[test]
+    fn parse_duration_arg_rejects_overflowing_values() {
+        assert_eq!(parse_duration_arg("90"), Ok(Duration::from_secs(90 * 60)));
+        assert_eq!(parse_duration_arg("1h30m"), Ok(Duration::from_secs(5400)));
+
+        assert!(parse_duration_arg("307445734561825861").is_err());
+        assert!(parse_duration_arg("5124095576030432h").is_err());
+        assert!(parse_duration_arg(&format!("{}s1s", u64::MAX)).is_err());
+    }
+
+    #[test]
+    fn ascii_fold_matches_all_three_umlaut_spellings() {
+        let results = vec![
+            item("ARD", "Krimi", "Käthe ermittelt", 90, 1_700_000_000),
+            item("ARD", "Krimi", "Kaethe ermittelt", 90, 1_700_000_100),
+            item("ARD", "Krimi", "Kathe ermittelt", 90, 1_700_000_200),
+        ];
+        let titles = |pattern: &str| -> Vec<String> {
+            apply_regex_filters(
+                results.clone(),
+                None,
+                Some(vec![pattern.to_string()]),
+                true,
+                false,
+                false,
+            )
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.title)
+            .collect()
+        };
+
+        assert_eq!(
+            titles("Käthe"),
+            ["Käthe ermittelt", "Kaethe ermittelt", "Kathe ermittelt"]
+        );
+        assert_eq!(titles("Kaethe"), ["Käthe ermittelt", "Kaethe ermittelt"]);
+        assert_eq!(titles("Kathe"), ["Käthe ermittelt", "Kathe ermittelt"]);
     }
 }
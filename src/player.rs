@@ -0,0 +1,185 @@
+//! Launching a media player with a generated playlist
+//!
+//! By default the playlist is opened in VLC, found on the `PATH` or in its
+//! usual install locations. `--player <PATH>` (or the `MWB_PLAYER` environment
+//! variable) starts another player instead, e.g. `mpv`, `iina` or any binary
+//! that takes a playlist file as its argument. `--player-args` appends extra
+//! arguments after the playlist, and `--no-launch` only writes the playlist.
+
+use anyhow::Result;
+use colored::Colorize;
+use std::process::{Child, Command};
+
+/// VLC executables tried in order when no player is configured
+const VLC_CANDIDATES: &[&str] = if cfg!(target_os = "windows") {
+    &[
+        "vlc",
+        "C:\\Program Files\\VideoLAN\\VLC\\vlc.exe",
+        "C:\\Program Files (x86)\\VideoLAN\\VLC\\vlc.exe",
+    ]
+} else if cfg!(target_os = "macos") {
+    &["vlc", "/Applications/VLC.app/Contents/MacOS/VLC"]
+} else {
+    &["vlc"]
+};
+
+/// Which player opens generated playlists
+#[derive(Debug, Clone, Default)]
+pub struct PlayerOptions {
+    /// Player binary from `--player` or `MWB_PLAYER`, `None` to look for VLC
+    pub command: Option<String>,
+    /// Extra arguments from `--player-args`, passed after the playlist
+    pub args: Vec<String>,
+    /// Write playlists without starting a player
+    pub no_launch: bool,
+}
+
+impl PlayerOptions {
+    /// Resolve the player: `--player`, then `MWB_PLAYER`, then VLC
+    ///
+    /// `args` is the `--player-args` string, split like a shell would.
+    pub fn new(flag: Option<String>, args: Option<&str>, no_launch: bool) -> Result<Self> {
+        let command = flag
+            .or_else(|| std::env::var("MWB_PLAYER").ok())
+            .filter(|command| !command.trim().is_empty());
+        let args = match args {
+            Some(args) => split_args(args)
+                .map_err(|e| anyhow::anyhow!("Invalid --player-args '{}': {}", args, e))?,
+            None => Vec::new(),
+        };
+
+        Ok(Self {
+            command,
+            args,
+            no_launch,
+        })
+    }
+
+    /// Name shown in messages
+    fn name(&self) -> &str {
+        self.command.as_deref().unwrap_or("VLC")
+    }
+
+    /// Play a single video URL, unlike a playlist there is nothing to fall back to
+    pub fn play(&self, url: &str) -> Result<()> {
+        self.spawn(url)
+            .map_err(|e| anyhow::anyhow!("Failed to launch {}: {}", self.name(), e))?;
+        Ok(())
+    }
+
+    /// Open a playlist in the player, returning whether one was started
+    ///
+    /// A player that can't be started isn't an error: the playlist is already
+    /// written, so the user is told where to find it instead.
+    pub fn launch(&self, playlist: &str) -> bool {
+        if self.no_launch {
+            tracing::info!(playlist = %playlist, "Not launching a player (--no-launch)");
+            return false;
+        }
+
+        println!("{}", format!("Launching {}...", self.name()).yellow());
+
+        match self.spawn(playlist) {
+            Ok(_) => {
+                println!(
+                    "{}",
+                    format!("{} launched successfully!", self.name()).green()
+                );
+                true
+            }
+            Err(e) => {
+                tracing::warn!(player = %self.name(), error = %e, "Failed to launch player");
+                println!("{}", format!("Failed to launch {}: {e}", self.name()).red());
+                println!("{}", format!("Playlist saved as: {playlist}").yellow());
+                println!(
+                    "{}",
+                    "You can open it manually, or pick a player with --player or MWB_PLAYER."
+                        .yellow()
+                );
+                false
+            }
+        }
+    }
+
+    /// Start the player on a playlist file or a video URL
+    fn spawn(&self, target: &str) -> std::io::Result<Child> {
+        match &self.command {
+            Some(command) => Command::new(command).arg(target).args(&self.args).spawn(),
+            None => {
+                // Keep the first error, it's the one for a plain `vlc` on the PATH
+                let mut first_error = None;
+                let mut spawned = None;
+                for candidate in VLC_CANDIDATES {
+                    match Command::new(candidate).arg(target).args(&self.args).spawn() {
+                        Ok(child) => {
+                            tracing::debug!(command = %candidate, "Found VLC");
+                            spawned = Some(child);
+                            break;
+                        }
+                        Err(e) => {
+                            first_error.get_or_insert(e);
+                        }
+                    }
+                }
+                spawned.ok_or_else(|| {
+                    first_error.unwrap_or_else(|| std::io::ErrorKind::NotFound.into())
+                })
+            }
+        }
+    }
+}
+
+/// Split an argument string into words, shell style
+///
+/// Whitespace separates words, `'...'` and `"..."` keep spaces inside a word.
+/// Within double quotes `\"` and `\\` stand for a quote and a backslash; other
+/// backslashes are kept as they are, so Windows paths need no escaping.
+fn split_args(input: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => current.push(c),
+                        None => return Err("unclosed single quote".to_string()),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') if matches!(chars.peek(), Some('"' | '\\')) => {
+                            current.extend(chars.next());
+                        }
+                        Some(c) => current.push(c),
+                        None => return Err("unclosed double quote".to_string()),
+                    }
+                }
+            }
+            c if c.is_whitespace() => {
+                if in_word {
+                    args.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            c => {
+                in_word = true;
+                current.push(c);
+            }
+        }
+    }
+    if in_word {
+        args.push(current);
+    }
+
+    Ok(args)
+}
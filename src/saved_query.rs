@@ -0,0 +1,201 @@
+//! Saved searches
+//!
+//! `-f json --with-meta` wraps the results in an envelope whose `meta` object
+//! records the query and the parameters that shaped the result set. Passing
+//! such a file to `--from-file` reads the parameters back and re-runs the exact
+//! search, so a result can be shared and regenerated by anyone.
+//!
+//! Only parameters that select results are recorded. Output options like the
+//! format or `-v` always come from the command line.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::{FieldPattern, SearchParams};
+
+/// Version of the saved query layout, bumped on incompatible changes
+pub const SAVED_QUERY_VERSION: u32 = 1;
+
+/// Query and search parameters recorded in `--with-meta` JSON output
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SavedQuery {
+    pub version: u32,
+    /// mwb version that produced the file
+    pub mwb_version: String,
+    pub query: Vec<String>,
+    #[serde(default)]
+    pub exclude: Option<Vec<String>>,
+    #[serde(default)]
+    pub include: Option<Vec<String>>,
+    /// Field-scoped regex filters (`--include-title` and friends)
+    #[serde(default)]
+    pub include_fields: Vec<FieldPattern>,
+    #[serde(default)]
+    pub exclude_fields: Vec<FieldPattern>,
+    pub size: u32,
+    pub offset: u32,
+    pub sort_by: String,
+    pub sort_order: String,
+    #[serde(default)]
+    pub exclude_future: bool,
+    /// Channels the search was restricted to by the config file
+    #[serde(default)]
+    pub default_channels: Vec<String>,
+    #[serde(default)]
+    pub topics: Vec<String>,
+    #[serde(default)]
+    pub exclude_topics: Vec<String>,
+    #[serde(default)]
+    pub channels: Vec<String>,
+    #[serde(default)]
+    pub exclude_channels: Vec<String>,
+    #[serde(default)]
+    pub channel_case_exact: bool,
+    #[serde(default)]
+    pub min_description_length: Option<usize>,
+    #[serde(default)]
+    pub ascii_fold: bool,
+    #[serde(default)]
+    pub case_sensitive: bool,
+    #[serde(default)]
+    pub no_live: bool,
+    #[serde(default)]
+    pub dedup: bool,
+    #[serde(default)]
+    pub dedup_fuzzy: bool,
+    #[serde(default)]
+    pub after: Option<chrono::NaiveDate>,
+    #[serde(default)]
+    pub before: Option<chrono::NaiveDate>,
+    /// Every page was fetched (`--all`), `size` is the page size
+    #[serde(default)]
+    pub all: bool,
+    #[serde(default)]
+    pub max_total_duration_secs: Option<u64>,
+    #[serde(default)]
+    pub subtract: Option<String>,
+    #[serde(default)]
+    pub sample: Option<usize>,
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+/// The part of a `--with-meta` file needed to re-run the search
+#[derive(Deserialize)]
+struct SavedQueryFile {
+    meta: SavedQuery,
+}
+
+impl SavedQuery {
+    /// Record the result-selecting parameters of a search
+    pub fn from_params(params: &SearchParams) -> Self {
+        Self {
+            version: SAVED_QUERY_VERSION,
+            mwb_version: env!("CARGO_PKG_VERSION").to_string(),
+            query: params.query_terms.clone(),
+            exclude: params.exclude_patterns.clone(),
+            include: params.include_patterns.clone(),
+            include_fields: params.include_fields.clone(),
+            exclude_fields: params.exclude_fields.clone(),
+            size: params.size,
+            offset: params.offset,
+            sort_by: params.sort_by.clone(),
+            sort_order: params.sort_order.clone(),
+            exclude_future: params.exclude_future,
+            default_channels: params.default_channels.clone(),
+            topics: params.topics.clone(),
+            exclude_topics: params.exclude_topics.clone(),
+            channels: params.channels.clone(),
+            exclude_channels: params.exclude_channels.clone(),
+            channel_case_exact: params.channel_case_exact,
+            min_description_length: params.min_description_length,
+            ascii_fold: params.ascii_fold,
+            case_sensitive: params.case_sensitive,
+            no_live: params.no_live,
+            dedup: params.dedup,
+            dedup_fuzzy: params.dedup_fuzzy,
+            after: params.after,
+            before: params.before,
+            all: params.all,
+            max_total_duration_secs: params.max_total_duration.map(|d| d.as_secs()),
+            subtract: params.subtract.clone(),
+            sample: params.sample,
+            seed: params.seed,
+        }
+    }
+
+    /// Read the saved query from a `--with-meta` JSON file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read saved query {}", path.display()))?;
+        let file: SavedQueryFile = serde_json::from_str(&content).with_context(|| {
+            format!(
+                "{} is not a saved query (expected JSON from -f json --with-meta)",
+                path.display()
+            )
+        })?;
+        let saved = file.meta;
+
+        if saved.version != SAVED_QUERY_VERSION {
+            anyhow::bail!(
+                "Saved query {} has version {}, this mwb only reads version {}",
+                path.display(),
+                saved.version,
+                SAVED_QUERY_VERSION
+            );
+        }
+
+        if saved.mwb_version != env!("CARGO_PKG_VERSION") {
+            eprintln!(
+                "{}",
+                format!(
+                    "⚠️  Saved query was written by mwb {}, this is mwb {}; results may differ",
+                    saved.mwb_version,
+                    env!("CARGO_PKG_VERSION")
+                )
+                .yellow()
+            );
+        }
+
+        tracing::info!(path = %path.display(), saved = ?saved, "Loaded saved query");
+
+        Ok(saved)
+    }
+
+    /// Replace the result-selecting parameters of a search with the saved ones
+    pub fn apply_to(self, params: &mut SearchParams) {
+        params.query_terms = self.query;
+        params.exclude_patterns = self.exclude;
+        params.include_patterns = self.include;
+        params.include_fields = self.include_fields;
+        params.exclude_fields = self.exclude_fields;
+        params.size = self.size;
+        params.offset = self.offset;
+        params.sort_by = self.sort_by;
+        params.sort_order = self.sort_order;
+        params.exclude_future = self.exclude_future;
+        params.default_channels = self.default_channels;
+        params.topics = self.topics;
+        params.exclude_topics = self.exclude_topics;
+        params.channels = self.channels;
+        params.exclude_channels = self.exclude_channels;
+        params.channel_case_exact = self.channel_case_exact;
+        params.min_description_length = self.min_description_length;
+        params.ascii_fold = self.ascii_fold;
+        params.case_sensitive = self.case_sensitive;
+        params.no_live = self.no_live;
+        params.dedup = self.dedup;
+        params.dedup_fuzzy = self.dedup_fuzzy;
+        params.after = self.after;
+        params.before = self.before;
+        params.all = self.all;
+        params.max_total_duration = self
+            .max_total_duration_secs
+            .map(std::time::Duration::from_secs);
+        params.subtract = self.subtract;
+        params.sample = self.sample;
+        params.seed = self.seed;
+    }
+}
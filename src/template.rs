@@ -0,0 +1,146 @@
+//! Custom one-line output with `-f template --template "<fmt>"`
+//!
+//! A template is plain text with `{field}` placeholders, rendered once per
+//! result, e.g. `--template "{date} {channel}: {title} <{url_hd}>"`. `{{` and
+//! `}}` print literal braces, `\t` and `\n` a tab and a newline. The template
+//! is checked when the command line is parsed, so a typo in a placeholder fails
+//! before any request is made.
+
+use chrono::DateTime;
+
+/// Placeholders a template may use
+pub const TEMPLATE_FIELDS: &[&str] = &[
+    "channel", "topic", "title", "date", "time", "duration", "url", "url_low", "url_hd", "website",
+    "subtitle", "desc", "id",
+];
+
+/// A parsed output template
+#[derive(Debug, Clone)]
+pub struct OutputTemplate {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone)]
+enum Segment {
+    Text(String),
+    /// One of `TEMPLATE_FIELDS`
+    Field(&'static str),
+}
+
+impl OutputTemplate {
+    /// Parse a template, rejecting unknown placeholders and unbalanced braces
+    pub fn parse(template: &str) -> Result<Self, String> {
+        let mut segments = Vec::new();
+        let mut text = String::new();
+        let mut chars = template.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => {
+                                return Err(format!(
+                                    "unclosed placeholder '{{{name}' in template, use {{{{ for a literal brace"
+                                ))
+                            }
+                        }
+                    }
+                    let field = TEMPLATE_FIELDS
+                        .iter()
+                        .find(|field| **field == name.trim())
+                        .ok_or_else(|| {
+                            format!(
+                                "unknown placeholder '{{{}}}' in template. Valid placeholders: {}",
+                                name,
+                                TEMPLATE_FIELDS.join(", ")
+                            )
+                        })?;
+                    if !text.is_empty() {
+                        segments.push(Segment::Text(std::mem::take(&mut text)));
+                    }
+                    segments.push(Segment::Field(field));
+                }
+                '}' => {
+                    return Err("unmatched '}' in template, use }} for a literal brace".to_string())
+                }
+                '\\' if chars.peek() == Some(&'t') => {
+                    chars.next();
+                    text.push('\t');
+                }
+                '\\' if chars.peek() == Some(&'n') => {
+                    chars.next();
+                    text.push('\n');
+                }
+                c => text.push(c),
+            }
+        }
+        if !text.is_empty() {
+            segments.push(Segment::Text(text));
+        }
+
+        Ok(Self { segments })
+    }
+
+    /// Render the template for one result, missing values render as empty text
+    pub fn render(&self, entry: &mediathekviewweb::models::Item) -> String {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Text(text) => text.clone(),
+                Segment::Field(field) => field_value(entry, field),
+            })
+            .collect()
+    }
+}
+
+fn field_value(entry: &mediathekviewweb::models::Item, field: &str) -> String {
+    let date_time = || DateTime::from_timestamp(entry.timestamp, 0);
+
+    match field {
+        "channel" => entry.channel.clone(),
+        "topic" => entry.topic.clone(),
+        "title" => entry.title.clone(),
+        "date" => date_time()
+            .map(|dt| dt.format("%Y-%m-%d").to_string())
+            .unwrap_or_default(),
+        "time" => date_time()
+            .map(|dt| dt.format("%H:%M").to_string())
+            .unwrap_or_default(),
+        "duration" => entry
+            .duration
+            .map(|d| format!("{}min", d.as_secs() / 60))
+            .unwrap_or_default(),
+        "url" => entry.url_video.clone(),
+        "url_low" => crate::select_video_url(entry, "l").to_string(),
+        "url_hd" => crate::select_video_url(entry, "h").to_string(),
+        "website" => entry.url_website.clone(),
+        "subtitle" => crate::subtitle_url(entry).unwrap_or_default().to_string(),
+        // One line per result, descriptions can span several
+        "desc" => entry
+            .description
+            .as_deref()
+            .unwrap_or_default()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" "),
+        "id" => entry.id.clone(),
+        _ => String::new(),
+    }
+}
+
+/// clap value parser for `--template`
+pub fn parse_template_arg(value: &str) -> Result<OutputTemplate, String> {
+    OutputTemplate::parse(value)
+}